@@ -6,7 +6,15 @@
 use bitflags::bitflags;
 // re-exported as used in the static_dict! macro implementation
 pub use spa_sys::spa_dict_item;
-use std::{ffi::CStr, fmt, marker::PhantomData, ptr};
+use std::{
+    cell::UnsafeCell,
+    collections::HashMap,
+    ffi::{CStr, CString},
+    fmt,
+    marker::PhantomData,
+    ops::Deref,
+    ptr,
+};
 
 /// Trait providing API to read dictionaries.
 pub trait ReadableDict {
@@ -260,6 +268,224 @@ impl fmt::Debug for ForeignDict {
     }
 }
 
+/// A transparent wrapper around a `spa_sys::spa_dict`, borrowed like a `&str`.
+///
+/// Unlike [`ForeignDict`], which wraps a pointer, a `&SpaDict` is a plain reference
+/// whose lifetime is tied to the dict it was created from.
+/// Its content is accessed through the [`ReadableDict`] methods, such as `iter()` and `get()`.
+#[repr(transparent)]
+pub struct SpaDict(spa_sys::spa_dict);
+
+impl SpaDict {
+    /// Create a `&SpaDict` from a raw `spa_dict` pointer.
+    ///
+    /// # Safety
+    /// - The provided pointer must point to a valid, well-aligned `spa_dict` struct.
+    /// - The dict and its items must not be mutated and must stay valid for the lifetime `'a`.
+    pub unsafe fn from_raw<'a>(dict: *const spa_sys::spa_dict) -> &'a Self {
+        &*(dict as *const SpaDict)
+    }
+
+    /// Obtain a pointer to the underlying `spa_dict`.
+    pub fn as_raw_ptr(&self) -> *const spa_sys::spa_dict {
+        &self.0
+    }
+}
+
+impl ReadableDict for SpaDict {
+    fn get_dict_ptr(&self) -> *const spa_sys::spa_dict {
+        self.as_raw_ptr()
+    }
+}
+
+impl fmt::Debug for SpaDict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.debug("SpaDict", f)
+    }
+}
+
+impl From<&SpaDict> for HashMap<String, String> {
+    fn from(dict: &SpaDict) -> Self {
+        dict.iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect()
+    }
+}
+
+/// A builder for dictionaries that can be passed to functions expecting a `spa_dict`.
+///
+/// Key-value pairs are added with [`add`](Self::add), and the dictionary is then created with
+/// [`build`](Self::build), allocating the items on the heap, or [`build_stack`](Self::build_stack),
+/// storing them in a fixed size array inside the dictionary itself.
+/// Both dictionaries dereference to [`SpaDict`].
+///
+/// # Examples
+/// ```rust
+/// use libspa::prelude::*;
+/// use libspa::dict::SpaDictBuilder;
+///
+/// let dict = SpaDictBuilder::new()
+///     .add("media.class", "Audio/Sink")
+///     .add("node.name", "sink")
+///     .build_stack::<4>();
+///
+/// assert_eq!(Some("sink"), dict.get("node.name"));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SpaDictBuilder {
+    items: Vec<(CString, CString)>,
+}
+
+impl SpaDictBuilder {
+    /// Create a new builder without any item.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a key-value pair to the dictionary.
+    ///
+    /// # Panics
+    /// If `key` or `value` contains a null byte.
+    #[must_use]
+    pub fn add(mut self, key: &str, value: &str) -> Self {
+        self.items.push((
+            CString::new(key).expect("Invalid byte in dict key"),
+            CString::new(value).expect("Invalid byte in dict value"),
+        ));
+        self
+    }
+
+    /// Create a dictionary whose items are allocated on the heap.
+    pub fn build(self) -> HeapDict {
+        let items: Vec<_> = self
+            .items
+            .iter()
+            .map(|(k, v)| spa_dict_item {
+                key: k.as_ptr(),
+                value: v.as_ptr(),
+            })
+            .collect();
+        let raw = spa_sys::spa_dict {
+            flags: Flags::empty().bits(),
+            n_items: items.len() as u32,
+            items: items.as_ptr(),
+        };
+
+        HeapDict {
+            raw,
+            _items: items,
+            _strings: self.items,
+        }
+    }
+
+    /// Create a dictionary storing its items in an array of `N` elements.
+    ///
+    /// The dictionary is returned by value, so the items are stored wherever it is placed,
+    /// such as on the stack.
+    /// The keys and values themselves are still allocated on the heap.
+    ///
+    /// # Panics
+    /// If more than `N` items were added.
+    pub fn build_stack<const N: usize>(self) -> StackDict<N> {
+        assert!(
+            self.items.len() <= N,
+            "Too many items for a StackDict<{}>: {}",
+            N,
+            self.items.len()
+        );
+
+        let mut items = [spa_dict_item {
+            key: ptr::null(),
+            value: ptr::null(),
+        }; N];
+        for (item, (k, v)) in items.iter_mut().zip(&self.items) {
+            item.key = k.as_ptr();
+            item.value = v.as_ptr();
+        }
+
+        StackDict {
+            // The items pointer is set when the dict is accessed, as the dict may still move until then.
+            raw: UnsafeCell::new(spa_sys::spa_dict {
+                flags: Flags::empty().bits(),
+                n_items: self.items.len() as u32,
+                items: ptr::null(),
+            }),
+            items,
+            _strings: self.items,
+        }
+    }
+}
+
+/// A dictionary with heap allocated items, created by [`SpaDictBuilder::build`].
+pub struct HeapDict {
+    raw: spa_sys::spa_dict,
+    // Pointed to by `raw`, the heap allocations do not move with the struct.
+    _items: Vec<spa_dict_item>,
+    _strings: Vec<(CString, CString)>,
+}
+
+impl Deref for HeapDict {
+    type Target = SpaDict;
+
+    fn deref(&self) -> &SpaDict {
+        unsafe { SpaDict::from_raw(&self.raw) }
+    }
+}
+
+impl ReadableDict for HeapDict {
+    fn get_dict_ptr(&self) -> *const spa_sys::spa_dict {
+        &self.raw
+    }
+}
+
+impl fmt::Debug for HeapDict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.debug("HeapDict", f)
+    }
+}
+
+/// A dictionary storing up to `N` items in an array, created by [`SpaDictBuilder::build_stack`].
+///
+/// As the `spa_dict` points into the array of items, which moves along with the dictionary,
+/// the pointer is updated each time the dictionary is accessed.
+/// A pointer returned by [`get_dict_ptr`](`ReadableDict::get_dict_ptr`) is thus only valid
+/// as long as the dictionary is not moved.
+pub struct StackDict<const N: usize> {
+    raw: UnsafeCell<spa_sys::spa_dict>,
+    items: [spa_dict_item; N],
+    _strings: Vec<(CString, CString)>,
+}
+
+impl<const N: usize> Deref for StackDict<N> {
+    type Target = SpaDict;
+
+    fn deref(&self) -> &SpaDict {
+        unsafe { SpaDict::from_raw(self.get_dict_ptr()) }
+    }
+}
+
+impl<const N: usize> ReadableDict for StackDict<N> {
+    fn get_dict_ptr(&self) -> *const spa_sys::spa_dict {
+        let raw = self.raw.get();
+        let items = self.items.as_ptr();
+        // Safety: The pointer can only be outdated if the dict has been moved since it was last
+        // accessed, in which case no reference to the raw dict can be alive anymore.
+        unsafe {
+            if (*raw).items != items {
+                (*raw).items = items;
+            }
+        }
+
+        raw
+    }
+}
+
+impl<const N: usize> fmt::Debug for StackDict<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.debug("StackDict", f)
+    }
+}
+
 bitflags! {
     /// Dictionary flags
     pub struct Flags: u32 {
@@ -445,7 +671,7 @@ unsafe impl Sync for StaticDict {}
 
 #[cfg(test)]
 mod tests {
-    use super::{Flags, ForeignDict, ReadableDict, StaticDict};
+    use super::{Flags, ForeignDict, ReadableDict, SpaDict, SpaDictBuilder, StaticDict};
     use spa_sys::spa_dict;
     use std::{ffi::CString, ptr};
 
@@ -654,4 +880,45 @@ mod tests {
         assert!(!ptr.is_null());
         parse_error!("badger", *const i32);
     }
+
+    #[test]
+    fn test_spa_dict() {
+        let dict = static_dict! {
+            "K0" => "V0",
+            "K1" => "V1"
+        };
+        let dict = unsafe { SpaDict::from_raw(dict.get_dict_ptr()) };
+
+        assert_eq!(Some("V1"), dict.get("K1"));
+        assert_eq!(None, dict.get("K2"));
+
+        let map: std::collections::HashMap<String, String> = dict.into();
+        assert_eq!(2, map.len());
+        assert_eq!(Some(&"V0".to_string()), map.get("K0"));
+    }
+
+    #[test]
+    fn test_dict_builder() {
+        let builder = SpaDictBuilder::new().add("K0", "V0").add("K1", "V1");
+
+        let heap = builder.clone().build();
+        assert_eq!(2, heap.len());
+        assert_eq!(Some("V0"), heap.get("K0"));
+
+        let stack = builder.build_stack::<3>();
+        assert_eq!(Some("V1"), stack.get("K1"));
+        // Moving the dict moves its items, which must still be found
+        let stack = Some(stack).unwrap();
+        assert_eq!(2, stack.len());
+        assert_eq!(
+            vec![("K0", "V0"), ("K1", "V1")],
+            stack.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dict_builder_too_many_items() {
+        let _ = SpaDictBuilder::new().add("K0", "V0").build_stack::<0>();
+    }
 }
@@ -16,6 +16,9 @@
 ///
 /// The macro returns whatever the called method returns, for example an `i32`, or `()` if the method returns nothing.
 ///
+/// If the `tracing` feature is enabled, a `trace` event with the `spa_call` target and the name of the method
+/// is emitted before each call.
+///
 /// # Examples
 /// Here we call the sync method on a `pipewire_sys::pw_core` object.
 /// ```
@@ -43,6 +46,76 @@ macro_rules! spa_interface_call_method {
         let funcs: *const $methods_struct = (*iface).cb.funcs.cast();
         let f = (*funcs).$method.unwrap();
 
+        $crate::__spa_trace_method!($method);
         f((*iface).cb.data, $($arg),*)
     }};
 }
+
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __spa_trace_method {
+    ($method:ident) => {
+        $crate::__tracing::trace!(target: "spa_call", method = stringify!($method));
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __spa_trace_method {
+    ($method:ident) => {};
+}
+
+/// Check that a spa_interface implements at least the provided version of its methods struct.
+///
+/// This needs to be called from within an `unsafe` block.
+///
+/// The macro takes three arguments:
+/// 1. A pointer to a C struct that can be casted to a spa_interface.
+/// 2. The type of the interfaces methods struct.
+/// 3. The minimum version of the methods struct required, such as the version in which a method was introduced.
+///
+/// Methods added in later versions of an interface are not present in the methods struct
+/// of implementations using an older version, so this should be checked before calling them
+/// with [`spa_interface_call_method!`].
+///
+/// The macro returns a `Result<(), Error>`, with an `ENOTSUP` [`Error`](crate::result::Error)
+/// if the version of the methods struct is lower than the required version,
+/// or if the interface has no methods struct.
+///
+/// # Safety
+/// The interface pointer must point to a valid spa_interface, whose `cb.funcs` is either null
+/// or points to a methods struct of the provided type, as the version is read from it.
+///
+/// # Examples
+/// ```no_run
+/// use pipewire_sys as pw_sys;
+/// use libspa as spa;
+///
+/// # let node: *mut pw_sys::pw_node = std::ptr::null_mut();
+/// let supported = unsafe { spa::verify_spa_interface_version!(node, pw_sys::pw_node_methods, 0) };
+/// assert!(supported.is_ok());
+/// ```
+#[macro_export]
+macro_rules! verify_spa_interface_version {
+    ($interface_ptr:expr, $methods_struct:ty, $required_version:expr) => {{
+        let iface: *mut spa_sys::spa_interface = $interface_ptr.cast();
+        let funcs: *const $methods_struct = (*iface).cb.funcs.cast();
+
+        $crate::interface::__check_version(
+            funcs.as_ref().map(|funcs| funcs.version),
+            $required_version,
+        )
+    }};
+}
+
+/// Check the version of a methods struct for [`verify_spa_interface_version!`],
+/// `version` being `None` if there is no methods struct.
+#[doc(hidden)]
+pub fn __check_version(version: Option<u32>, required: u32) -> Result<(), crate::result::Error> {
+    match version {
+        Some(version) if version >= required => Ok(()),
+        _ => Err(crate::result::Error::new(libc::ENOTSUP)),
+    }
+}
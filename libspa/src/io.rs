@@ -0,0 +1,342 @@
+// Copyright The pipewire-rs Contributors.
+// SPDX-License-Identifier: MIT
+
+//! IO areas, memory shared between the graph and a node to exchange data while it is running.
+//!
+//! IO areas are identified by one of the `SPA_IO_*` ids of this module, and are
+//! received as raw pointers, for example in the `io_changed` event of a stream
+//! or the `process` event of a filter.
+//! The wrappers of this module can be created from those pointers to safely read the areas.
+
+use std::slice;
+
+use crate::utils::Fraction;
+
+/// Id of an invalid IO area.
+pub const SPA_IO_INVALID: u32 = spa_sys::SPA_IO_Invalid;
+/// Id of the `spa_io_buffers` area, holding the buffers exchanged with a port.
+pub const SPA_IO_BUFFERS: u32 = spa_sys::SPA_IO_Buffers;
+/// Id of the `spa_io_range` area, holding the range of data requested by a port.
+pub const SPA_IO_RANGE: u32 = spa_sys::SPA_IO_Range;
+/// Id of the [`IoClock`] area.
+pub const SPA_IO_CLOCK: u32 = spa_sys::SPA_IO_Clock;
+/// Id of the `spa_io_latency` area.
+pub const SPA_IO_LATENCY: u32 = spa_sys::SPA_IO_Latency;
+/// Id of the `spa_io_sequence` area, holding control messages for a port.
+pub const SPA_IO_CONTROL: u32 = spa_sys::SPA_IO_Control;
+/// Id of the `spa_io_sequence` area, holding control messages produced by a port.
+pub const SPA_IO_NOTIFY: u32 = spa_sys::SPA_IO_Notify;
+/// Id of the [`IoPosition`] area.
+pub const SPA_IO_POSITION: u32 = spa_sys::SPA_IO_Position;
+/// Id of the [`IoRateMatch`] area.
+pub const SPA_IO_RATE_MATCH: u32 = spa_sys::SPA_IO_RateMatch;
+/// Id of the `spa_io_memory` area, holding a memory block of a port.
+pub const SPA_IO_MEMORY: u32 = spa_sys::SPA_IO_Memory;
+
+/// The maximum number of segments in an [`IoPosition`].
+pub const MAX_SEGMENTS: usize = spa_sys::SPA_IO_POSITION_MAX_SEGMENTS as usize;
+
+/// A transparent wrapper around a `spa_sys::spa_io_clock`, describing the clock driving the graph.
+#[repr(transparent)]
+pub struct IoClock(spa_sys::spa_io_clock);
+
+impl IoClock {
+    /// Create an `&IoClock` from a raw `spa_io_clock` pointer.
+    ///
+    /// # Safety
+    /// The provided pointer must point to a valid, well-aligned `spa_io_clock`
+    /// that stays valid for the lifetime `'a`.
+    pub unsafe fn from_raw<'a>(clock: *const spa_sys::spa_io_clock) -> &'a Self {
+        &*(clock as *const Self)
+    }
+
+    /// The raw representation of the clock.
+    pub fn as_raw(&self) -> &spa_sys::spa_io_clock {
+        &self.0
+    }
+
+    /// Get the raw `SPA_IO_CLOCK_FLAG_*` flags.
+    pub fn flags(&self) -> u32 {
+        self.0.flags
+    }
+
+    /// Get the unique id of the clock.
+    pub fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    /// Get the name of the clock.
+    ///
+    /// Returns `None` if the name is not valid UTF-8.
+    pub fn name(&self) -> Option<&str> {
+        let name = &self.0.name;
+        // Safety: `c_char` and `u8` have the same size and alignment.
+        let bytes = unsafe { slice::from_raw_parts(name.as_ptr() as *const u8, name.len()) };
+        let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..len]).ok()
+    }
+
+    /// Get the time of the start of the current cycle, in nanoseconds of the monotonic clock.
+    pub fn nsec(&self) -> u64 {
+        self.0.nsec
+    }
+
+    /// Get the rate of the clock, the unit of [`position`](Self::position) and [`duration`](Self::duration).
+    pub fn rate(&self) -> Fraction {
+        self.0.rate.into()
+    }
+
+    /// Get the current position of the clock, in units of [`rate`](Self::rate).
+    pub fn position(&self) -> u64 {
+        self.0.position
+    }
+
+    /// Get the duration of the current cycle, in units of [`rate`](Self::rate).
+    pub fn duration(&self) -> u64 {
+        self.0.duration
+    }
+
+    /// Get the delay between the position and the hardware, in units of [`rate`](Self::rate).
+    pub fn delay(&self) -> i64 {
+        self.0.delay
+    }
+
+    /// Get the rate difference between the clock and the monotonic clock.
+    pub fn rate_diff(&self) -> f64 {
+        self.0.rate_diff
+    }
+
+    /// Get the estimated time of the start of the next cycle, in nanoseconds of the monotonic clock.
+    pub fn next_nsec(&self) -> u64 {
+        self.0.next_nsec
+    }
+}
+
+/// A transparent wrapper around a `spa_sys::spa_io_segment`,
+/// describing how the clock position maps to a stream position.
+#[repr(transparent)]
+pub struct IoSegment(spa_sys::spa_io_segment);
+
+impl IoSegment {
+    /// Create an `&IoSegment` from a raw `spa_io_segment` pointer.
+    ///
+    /// # Safety
+    /// The provided pointer must point to a valid, well-aligned `spa_io_segment`
+    /// that stays valid for the lifetime `'a`.
+    pub unsafe fn from_raw<'a>(segment: *const spa_sys::spa_io_segment) -> &'a Self {
+        &*(segment as *const Self)
+    }
+
+    /// The raw representation of the segment.
+    pub fn as_raw(&self) -> &spa_sys::spa_io_segment {
+        &self.0
+    }
+
+    /// Get the version of the segment.
+    pub fn version(&self) -> u32 {
+        self.0.version
+    }
+
+    /// Get the raw `SPA_IO_SEGMENT_FLAG_*` flags.
+    pub fn flags(&self) -> u32 {
+        self.0.flags
+    }
+
+    /// Get the clock position at which the segment starts.
+    pub fn start(&self) -> u64 {
+        self.0.start
+    }
+
+    /// Get the duration of the segment, or 0 if it lasts until the next one.
+    pub fn duration(&self) -> u64 {
+        self.0.duration
+    }
+
+    /// Get the playback rate of the segment.
+    pub fn rate(&self) -> f64 {
+        self.0.rate
+    }
+
+    /// Get the stream position at the start of the segment.
+    pub fn position(&self) -> u64 {
+        self.0.position
+    }
+
+    /// Get the bar information of the segment.
+    pub fn bar(&self) -> &spa_sys::spa_io_segment_bar {
+        &self.0.bar
+    }
+
+    /// Get the video information of the segment.
+    pub fn video(&self) -> &spa_sys::spa_io_segment_video {
+        &self.0.video
+    }
+}
+
+/// The state of the transport of an [`IoPosition`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IoPositionState {
+    /// The transport is stopped.
+    Stopped,
+    /// The transport is starting.
+    Starting,
+    /// The transport is running.
+    Running,
+}
+
+impl IoPositionState {
+    /// The raw representation of the state.
+    pub fn as_raw(&self) -> spa_sys::spa_io_position_state {
+        match self {
+            Self::Stopped => spa_sys::SPA_IO_POSITION_STATE_STOPPED,
+            Self::Starting => spa_sys::SPA_IO_POSITION_STATE_STARTING,
+            Self::Running => spa_sys::SPA_IO_POSITION_STATE_RUNNING,
+        }
+    }
+
+    /// Create an `IoPositionState` from a raw `spa_io_position_state`.
+    ///
+    /// Returns `None` if `raw` is not a known state.
+    pub fn from_raw(raw: spa_sys::spa_io_position_state) -> Option<Self> {
+        match raw {
+            spa_sys::SPA_IO_POSITION_STATE_STOPPED => Some(Self::Stopped),
+            spa_sys::SPA_IO_POSITION_STATE_STARTING => Some(Self::Starting),
+            spa_sys::SPA_IO_POSITION_STATE_RUNNING => Some(Self::Running),
+            _ => None,
+        }
+    }
+}
+
+/// A transparent wrapper around a `spa_sys::spa_io_position`, describing the position of the graph.
+///
+/// It is received in the `process` event of a filter, or in the `io_changed` event
+/// of a stream with the [`SPA_IO_POSITION`] id.
+#[repr(transparent)]
+pub struct IoPosition(spa_sys::spa_io_position);
+
+impl IoPosition {
+    /// Create an `&IoPosition` from a raw `spa_io_position` pointer.
+    ///
+    /// # Safety
+    /// The provided pointer must point to a valid, well-aligned `spa_io_position`
+    /// that stays valid for the lifetime `'a`.
+    pub unsafe fn from_raw<'a>(position: *const spa_sys::spa_io_position) -> &'a Self {
+        &*(position as *const Self)
+    }
+
+    /// The raw representation of the position.
+    pub fn as_raw(&self) -> &spa_sys::spa_io_position {
+        &self.0
+    }
+
+    /// Get the clock driving the graph.
+    pub fn clock(&self) -> &IoClock {
+        // Safety: `IoClock` is a transparent wrapper around `spa_io_clock`.
+        unsafe { IoClock::from_raw(&self.0.clock) }
+    }
+
+    /// Get the size of the video, if any.
+    pub fn video(&self) -> &spa_sys::spa_io_video_size {
+        &self.0.video
+    }
+
+    /// Get the offset subtracted from the clock position to get the stream position.
+    pub fn offset(&self) -> i64 {
+        self.0.offset
+    }
+
+    /// Get the state of the transport.
+    ///
+    /// Returns `None` if the state is not known.
+    pub fn state(&self) -> Option<IoPositionState> {
+        IoPositionState::from_raw(self.0.state)
+    }
+
+    /// Get the valid segments of the position.
+    pub fn segments(&self) -> &[IoSegment] {
+        let n_segments = (self.0.n_segments as usize).min(MAX_SEGMENTS);
+        // Safety: `IoSegment` is a transparent wrapper around `spa_io_segment`.
+        unsafe { slice::from_raw_parts(self.0.segments.as_ptr() as *const IoSegment, n_segments) }
+    }
+}
+
+/// A transparent wrapper around a `spa_sys::spa_io_rate_match`, used for rate compensation.
+///
+/// It is received in the `io_changed` event of a stream with the [`SPA_IO_RATE_MATCH`] id.
+#[repr(transparent)]
+pub struct IoRateMatch(spa_sys::spa_io_rate_match);
+
+impl IoRateMatch {
+    /// Create an `&IoRateMatch` from a raw `spa_io_rate_match` pointer.
+    ///
+    /// # Safety
+    /// The provided pointer must point to a valid, well-aligned `spa_io_rate_match`
+    /// that stays valid for the lifetime `'a`.
+    pub unsafe fn from_raw<'a>(rate_match: *const spa_sys::spa_io_rate_match) -> &'a Self {
+        &*(rate_match as *const Self)
+    }
+
+    /// The raw representation of the rate match.
+    pub fn as_raw(&self) -> &spa_sys::spa_io_rate_match {
+        &self.0
+    }
+
+    /// Get the extra delay in samples introduced by the resampler.
+    pub fn delay(&self) -> u32 {
+        self.0.delay
+    }
+
+    /// Get the requested number of samples for the current cycle.
+    pub fn size(&self) -> u32 {
+        self.0.size
+    }
+
+    /// Get the rate correction to apply.
+    pub fn rate(&self) -> f64 {
+        self.0.rate
+    }
+
+    /// Get the raw `SPA_IO_RATE_MATCH_FLAG_*` flags.
+    pub fn flags(&self) -> u32 {
+        self.0.flags
+    }
+
+    /// Check if rate matching is active.
+    pub fn is_active(&self) -> bool {
+        self.0.flags & spa_sys::SPA_IO_RATE_MATCH_FLAG_ACTIVE != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::raw::c_char;
+
+    #[test]
+    fn clock_name() {
+        let mut raw: spa_sys::spa_io_clock = unsafe { std::mem::zeroed() };
+        for (c, b) in raw.name.iter_mut().zip(b"freewheel") {
+            *c = *b as c_char;
+        }
+        let clock = unsafe { IoClock::from_raw(&raw) };
+        assert_eq!(clock.name(), Some("freewheel"));
+    }
+
+    #[test]
+    fn position_segments() {
+        let mut raw: spa_sys::spa_io_position = unsafe { std::mem::zeroed() };
+        raw.state = spa_sys::SPA_IO_POSITION_STATE_RUNNING;
+        raw.n_segments = 1;
+        raw.segments[0].rate = 1.0;
+        raw.segments[1].rate = 2.0;
+
+        let position = unsafe { IoPosition::from_raw(&raw) };
+        assert_eq!(position.state(), Some(IoPositionState::Running));
+        assert_eq!(position.segments().len(), 1);
+        assert_eq!(position.segments()[0].rate(), 1.0);
+
+        raw.n_segments = u32::MAX;
+        let position = unsafe { IoPosition::from_raw(&raw) };
+        assert_eq!(position.segments().len(), MAX_SEGMENTS);
+    }
+}
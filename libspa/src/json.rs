@@ -0,0 +1,541 @@
+// Copyright The pipewire-rs Contributors.
+// SPDX-License-Identifier: MIT
+
+//! SPA JSON
+//!
+//! SPA JSON is the relaxed JSON format used by PipeWire configuration files and some metadata values.
+//! Compared to regular JSON, keys and values don't need to be quoted, `=` can be used instead of `:`,
+//! commas are optional and `#` starts a comment running to the end of the line.
+//!
+//! It is read with a [`JsonParser`] and written with a [`JsonBuilder`].
+
+use std::fmt::{self, Write};
+
+/// A parser for SPA JSON, equivalent to the C `spa_json` iterator.
+///
+/// Values are read one after the other with the `get_*` methods,
+/// and objects and arrays are entered to get a parser over their content.
+/// The keys and values of an object are read alternately, keys with [`get_string`](Self::get_string).
+///
+/// # Examples
+/// ```
+/// use libspa::json::JsonParser;
+///
+/// let mut parser = JsonParser::new("{ name = foo rate: 48000 }");
+/// let mut object = parser.enter_object().unwrap();
+///
+/// assert_eq!(object.get_string().as_deref(), Some("name"));
+/// assert_eq!(object.get_string().as_deref(), Some("foo"));
+/// assert_eq!(object.get_string().as_deref(), Some("rate"));
+/// assert_eq!(object.get_int(), Some(48000));
+/// assert_eq!(object.get_string(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonParser<'a> {
+    data: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    /// Create a parser reading the values of `data`.
+    pub fn new(data: &'a str) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Whether all values have been read.
+    pub fn is_empty(&self) -> bool {
+        self.clone().next_token().is_none()
+    }
+
+    /// Read the next value as a string, unescaping it if it is quoted.
+    ///
+    /// Like in the C API, unquoted values are returned as is.
+    /// Returns `None` if there is no value left or if the next value is an object or array,
+    /// in which case it is not consumed.
+    pub fn get_string(&mut self) -> Option<String> {
+        self.parse_next(|token| {
+            if token.starts_with('"') {
+                unescape(&token[1..token.len() - 1])
+            } else if token.starts_with(['{', '['].as_ref()) {
+                None
+            } else {
+                Some(token.to_string())
+            }
+        })
+    }
+
+    /// Read the next value as a boolean, `true` or `false`.
+    ///
+    /// Returns `None` if there is no value left or if it is not a boolean, in which case it is not consumed.
+    pub fn get_bool(&mut self) -> Option<bool> {
+        self.parse_next(|token| match token {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        })
+    }
+
+    /// Read the next value as an integer.
+    ///
+    /// Returns `None` if there is no value left or if it is not an integer, in which case it is not consumed.
+    pub fn get_int(&mut self) -> Option<i32> {
+        self.parse_next(|token| token.parse().ok())
+    }
+
+    /// Read the next value as a float.
+    ///
+    /// Returns `None` if there is no value left or if it is not a number, in which case it is not consumed.
+    pub fn get_float(&mut self) -> Option<f32> {
+        self.parse_next(|token| token.parse().ok())
+    }
+
+    /// Check if the next value is `null`, consuming it if it is.
+    pub fn get_null(&mut self) -> bool {
+        self.parse_next(|token| if token == "null" { Some(()) } else { None })
+            .is_some()
+    }
+
+    /// Enter the next value, which must be an object, returning a parser over its keys and values.
+    ///
+    /// The whole object is consumed from this parser.
+    /// Returns `None` if there is no value left or if it is not an object, in which case it is not consumed.
+    pub fn enter_object(&mut self) -> Option<JsonParser<'a>> {
+        self.enter('{')
+    }
+
+    /// Enter the next value, which must be an array, returning a parser over its values.
+    ///
+    /// The whole array is consumed from this parser.
+    /// Returns `None` if there is no value left or if it is not an array, in which case it is not consumed.
+    pub fn enter_array(&mut self) -> Option<JsonParser<'a>> {
+        self.enter('[')
+    }
+
+    /// Skip the next value, whatever its type, returning `false` if there is no value left.
+    pub fn skip(&mut self) -> bool {
+        self.next_token().is_some()
+    }
+
+    fn enter(&mut self, open: char) -> Option<JsonParser<'a>> {
+        self.parse_next(|token| {
+            if token.starts_with(open) {
+                Some(JsonParser::new(&token[1..token.len() - 1]))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Read the next token and convert it with `f`, only consuming the token if that succeeds.
+    fn parse_next<T>(&mut self, f: impl FnOnce(&'a str) -> Option<T>) -> Option<T> {
+        let pos = self.pos;
+        let value = self.next_token().and_then(f);
+        if value.is_none() {
+            self.pos = pos;
+        }
+        value
+    }
+
+    /// Read the next token: a quoted string including its quotes, a whole object or array,
+    /// or an unquoted value.
+    fn next_token(&mut self) -> Option<&'a str> {
+        let bytes = self.data.as_bytes();
+
+        // Skip separators and comments.
+        loop {
+            match *bytes.get(self.pos)? {
+                b' ' | b'\t' | b'\n' | b'\r' | b':' | b',' | b'=' => self.pos += 1,
+                b'#' => self.pos = skip_comment(bytes, self.pos),
+                _ => break,
+            }
+        }
+
+        let start = self.pos;
+        let end = match bytes[start] {
+            b'{' | b'[' => container_end(bytes, start)?,
+            b'"' => string_end(bytes, start)?,
+            // Unbalanced end of a container.
+            b'}' | b']' => return None,
+            _ => bytes[start..]
+                .iter()
+                .position(|b| {
+                    matches!(
+                        b,
+                        b' ' | b'\t' | b'\n' | b'\r' | b':' | b',' | b'=' | b'}' | b']'
+                    )
+                })
+                .map_or(bytes.len(), |len| start + len),
+        };
+
+        self.pos = end;
+        Some(&self.data[start..end])
+    }
+}
+
+/// A builder writing SPA JSON, such as the arguments of a module or a configuration fragment.
+///
+/// Values are appended with the `push_*` methods, and objects and arrays are opened and closed around them.
+/// The keys and values of an object are pushed alternately, keys with [`push_string`](Self::push_string).
+///
+/// The output is regular JSON, which is also valid SPA JSON.
+///
+/// # Examples
+/// ```
+/// use libspa::json::JsonBuilder;
+///
+/// let mut builder = JsonBuilder::new();
+/// builder.open_object();
+/// builder.push_string("node.name");
+/// builder.push_string("my-sink");
+/// builder.push_string("audio.position");
+/// builder.open_array();
+/// builder.push_string("FL");
+/// builder.push_string("FR");
+/// builder.close_array();
+/// builder.close_object();
+///
+/// assert_eq!(
+///     builder.to_string(),
+///     r#"{ "node.name": "my-sink", "audio.position": [ "FL", "FR" ] }"#
+/// );
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct JsonBuilder {
+    out: String,
+    /// The open containers, whether each is an object, and the number of values pushed to them.
+    containers: Vec<(bool, usize)>,
+    /// The number of top-level values.
+    count: usize,
+}
+
+impl JsonBuilder {
+    /// Create a builder with an empty output.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a builder appending to `buffer`, reusing its allocation.
+    pub fn with_buffer(buffer: String) -> Self {
+        let count = usize::from(!buffer.is_empty());
+        Self {
+            out: buffer,
+            containers: Vec::new(),
+            count,
+        }
+    }
+
+    /// Get the output written so far.
+    pub fn as_str(&self) -> &str {
+        &self.out
+    }
+
+    /// Consume the builder, returning its output.
+    ///
+    /// # Panics
+    /// If an object or array is still open.
+    pub fn into_string(self) -> String {
+        assert!(
+            self.containers.is_empty(),
+            "An object or array is still open"
+        );
+        self.out
+    }
+
+    /// Append a string, quoting and escaping it.
+    pub fn push_string(&mut self, value: &str) {
+        self.begin_value();
+        self.out.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\r' => self.out.push_str("\\r"),
+                '\t' => self.out.push_str("\\t"),
+                '\u{8}' => self.out.push_str("\\b"),
+                '\u{c}' => self.out.push_str("\\f"),
+                c if c.is_control() => write!(self.out, "\\u{:04x}", c as u32).unwrap(),
+                c => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+
+    /// Append a boolean.
+    pub fn push_bool(&mut self, value: bool) {
+        self.push_raw(if value { "true" } else { "false" });
+    }
+
+    /// Append an integer.
+    pub fn push_int(&mut self, value: i32) {
+        self.begin_value();
+        write!(self.out, "{}", value).unwrap();
+    }
+
+    /// Append a float.
+    ///
+    /// As they can't be represented in JSON, infinite and NaN values are written as `null`.
+    pub fn push_float(&mut self, value: f32) {
+        if value.is_finite() {
+            self.begin_value();
+            write!(self.out, "{}", value).unwrap();
+        } else {
+            self.push_null();
+        }
+    }
+
+    /// Append `null`.
+    pub fn push_null(&mut self) {
+        self.push_raw("null");
+    }
+
+    /// Open an object. The values pushed until [`close_object`](Self::close_object) are its keys and values.
+    pub fn open_object(&mut self) {
+        self.push_raw("{");
+        self.containers.push((true, 0));
+    }
+
+    /// Close the object opened last.
+    ///
+    /// # Panics
+    /// If the container opened last is not an object, or if the last key has no value.
+    pub fn close_object(&mut self) {
+        match self.containers.pop() {
+            Some((true, count)) => {
+                assert!(
+                    count % 2 == 0,
+                    "Missing value for the last key of the object"
+                );
+                self.out.push_str(if count == 0 { "}" } else { " }" });
+            }
+            _ => panic!("No object is open"),
+        }
+    }
+
+    /// Open an array. The values pushed until [`close_array`](Self::close_array) are its elements.
+    pub fn open_array(&mut self) {
+        self.push_raw("[");
+        self.containers.push((false, 0));
+    }
+
+    /// Close the array opened last.
+    ///
+    /// # Panics
+    /// If the container opened last is not an array.
+    pub fn close_array(&mut self) {
+        match self.containers.pop() {
+            Some((false, count)) => self.out.push_str(if count == 0 { "]" } else { " ]" }),
+            _ => panic!("No array is open"),
+        }
+    }
+
+    fn push_raw(&mut self, value: &str) {
+        self.begin_value();
+        self.out.push_str(value);
+    }
+
+    /// Write the separator needed before the next value.
+    fn begin_value(&mut self) {
+        let separator = match self.containers.last_mut() {
+            Some((is_object, count)) => {
+                let separator = match (*count, *is_object && *count % 2 == 1) {
+                    (_, true) => ": ",
+                    (0, false) => " ",
+                    (_, false) => ", ",
+                };
+                *count += 1;
+                separator
+            }
+            None => {
+                self.count += 1;
+                if self.count > 1 {
+                    " "
+                } else {
+                    ""
+                }
+            }
+        };
+        self.out.push_str(separator);
+    }
+}
+
+impl fmt::Display for JsonBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.out)
+    }
+}
+
+/// Get the position after the comment starting at `start`, at the end of the line.
+fn skip_comment(bytes: &[u8], start: usize) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(bytes.len(), |len| start + len)
+}
+
+/// Get the position after the closing quote of the string starting at `start`.
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut pos = start + 1;
+    loop {
+        match *bytes.get(pos)? {
+            b'\\' => pos += 2,
+            b'"' => return Some(pos + 1),
+            _ => pos += 1,
+        }
+    }
+}
+
+/// Get the position after the end of the object or array starting at `start`.
+fn container_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut pos = start;
+    loop {
+        match *bytes.get(pos)? {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(pos + 1);
+                }
+            }
+            b'"' => {
+                pos = string_end(bytes, pos)?;
+                continue;
+            }
+            b'#' => {
+                pos = skip_comment(bytes, pos);
+                continue;
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+}
+
+/// Unescape the content of a quoted string.
+fn unescape(s: &str) -> Option<String> {
+    let mut res = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+
+        let c = match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'u' => {
+                let hex: String = chars.by_ref().take(4).collect();
+                let code = u32::from_str_radix(&hex, 16).ok()?;
+                char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+            }
+            // Including `"`, `\` and `/`
+            c => c,
+        };
+        res.push(c);
+    }
+
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values() {
+        let mut parser = JsonParser::new(r#"true, 42 -1.5 null "a\"b\né" bare"#);
+
+        assert_eq!(parser.get_int(), None);
+        assert_eq!(parser.get_bool(), Some(true));
+        assert_eq!(parser.get_bool(), None);
+        assert_eq!(parser.get_int(), Some(42));
+        assert_eq!(parser.get_int(), None);
+        assert_eq!(parser.get_float(), Some(-1.5));
+        assert!(parser.get_null());
+        assert_eq!(parser.get_string().as_deref(), Some("a\"b\n\u{e9}"));
+        assert_eq!(parser.get_string().as_deref(), Some("bare"));
+        assert!(parser.is_empty());
+        assert_eq!(parser.get_string(), None);
+    }
+
+    #[test]
+    fn containers() {
+        let mut parser = JsonParser::new(
+            r#"
+            # A comment with a { brace
+            { key = [ 1 2 "]" ], nested: { a = b } } after
+            "#,
+        );
+
+        let mut object = parser.enter_object().unwrap();
+        assert_eq!(parser.get_string().as_deref(), Some("after"));
+        assert!(parser.is_empty());
+
+        assert_eq!(object.get_string().as_deref(), Some("key"));
+        assert_eq!(object.get_string(), None);
+        let mut array = object.enter_array().unwrap();
+        assert_eq!(array.get_int(), Some(1));
+        assert_eq!(array.get_int(), Some(2));
+        assert_eq!(array.get_string().as_deref(), Some("]"));
+        assert!(array.is_empty());
+
+        assert_eq!(object.get_string().as_deref(), Some("nested"));
+        assert!(object.enter_array().is_none());
+        assert!(object.skip());
+        assert!(object.is_empty());
+    }
+
+    #[test]
+    fn unterminated() {
+        assert!(JsonParser::new("{ a = b").enter_object().is_none());
+        assert_eq!(JsonParser::new(r#""foo"#).get_string(), None);
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = JsonBuilder::new();
+        builder.open_object();
+        builder.push_string("key \"1\"");
+        builder.open_array();
+        builder.push_int(-3);
+        builder.push_float(0.5);
+        builder.push_float(f32::NAN);
+        builder.push_bool(false);
+        builder.close_array();
+        builder.push_string("empty");
+        builder.open_object();
+        builder.close_object();
+        builder.close_object();
+        builder.push_null();
+
+        let json = builder.into_string();
+        assert_eq!(
+            json,
+            r#"{ "key \"1\"": [ -3, 0.5, null, false ], "empty": {} } null"#
+        );
+
+        let mut parser = JsonParser::new(&json);
+        let mut object = parser.enter_object().unwrap();
+        assert_eq!(object.get_string().as_deref(), Some("key \"1\""));
+        let mut array = object.enter_array().unwrap();
+        assert_eq!(array.get_int(), Some(-3));
+        assert_eq!(array.get_float(), Some(0.5));
+        assert!(array.get_null());
+        assert_eq!(array.get_bool(), Some(false));
+        assert!(parser.get_null());
+    }
+
+    #[test]
+    #[should_panic]
+    fn builder_mismatch() {
+        let mut builder = JsonBuilder::new();
+        builder.open_array();
+        builder.close_object();
+    }
+}
@@ -13,12 +13,21 @@ pub use result::*;
 mod direction;
 pub mod hook;
 pub mod interface;
+pub mod io;
+pub mod json;
 pub mod list;
+pub mod param;
 pub mod pod;
+pub mod ringbuffer;
 pub mod utils;
 pub use direction::*;
 pub mod flags;
 
+// Used by spa_interface_call_method!() when the `tracing` feature is enabled.
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing as __tracing;
+
 /// prelude module re-exporing all the traits providing public API.
 pub mod prelude {
     pub use crate::dict::{ReadableDict, WritableDict};
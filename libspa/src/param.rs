@@ -0,0 +1,74 @@
+//! Types for the parameters of SPA objects, such as the formats negotiated on ports.
+
+pub mod audio;
+pub mod format;
+pub mod latency;
+pub mod video;
+
+use std::fmt;
+
+/// The id of a parameter, such as [`ParamType::FORMAT`] for the format of a port.
+///
+/// This is a transparent wrapper around a raw `SPA_PARAM_*` id,
+/// so ids that are not known by this crate are preserved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ParamType(pub u32);
+
+#[allow(missing_docs)]
+impl ParamType {
+    pub const INVALID: Self = Self(spa_sys::SPA_PARAM_Invalid);
+    pub const PROP_INFO: Self = Self(spa_sys::SPA_PARAM_PropInfo);
+    pub const PROPS: Self = Self(spa_sys::SPA_PARAM_Props);
+    pub const ENUM_FORMAT: Self = Self(spa_sys::SPA_PARAM_EnumFormat);
+    pub const FORMAT: Self = Self(spa_sys::SPA_PARAM_Format);
+    pub const BUFFERS: Self = Self(spa_sys::SPA_PARAM_Buffers);
+    pub const META: Self = Self(spa_sys::SPA_PARAM_Meta);
+    pub const IO: Self = Self(spa_sys::SPA_PARAM_IO);
+    pub const ENUM_PROFILE: Self = Self(spa_sys::SPA_PARAM_EnumProfile);
+    pub const PROFILE: Self = Self(spa_sys::SPA_PARAM_Profile);
+    pub const ENUM_PORT_CONFIG: Self = Self(spa_sys::SPA_PARAM_EnumPortConfig);
+    pub const PORT_CONFIG: Self = Self(spa_sys::SPA_PARAM_PortConfig);
+    pub const ENUM_ROUTE: Self = Self(spa_sys::SPA_PARAM_EnumRoute);
+    pub const ROUTE: Self = Self(spa_sys::SPA_PARAM_Route);
+    pub const CONTROL: Self = Self(spa_sys::SPA_PARAM_Control);
+    pub const LATENCY: Self = Self(spa_sys::SPA_PARAM_Latency);
+    pub const PROCESS_LATENCY: Self = Self(spa_sys::SPA_PARAM_ProcessLatency);
+
+    /// The raw representation of the id.
+    pub fn as_raw(&self) -> u32 {
+        self.0
+    }
+
+    /// Create a `ParamType` from a raw `SPA_PARAM_*` id.
+    pub fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+}
+
+/// Error returned when parsing a parameter value, such as a format, from an unknown name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNameError {
+    name: String,
+}
+
+impl ParseNameError {
+    pub(crate) fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+        }
+    }
+
+    /// The name that could not be parsed.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl std::error::Error for ParseNameError {}
+
+impl fmt::Display for ParseNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown name '{}'", self.name)
+    }
+}
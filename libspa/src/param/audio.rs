@@ -0,0 +1,852 @@
+//! Audio parameters.
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use super::{
+    format::{MediaSubtype, MediaType},
+    ParamType, ParseNameError,
+};
+use crate::pod::{builder::PodBuilder, parser::PodParser, Pod, PropertyFlags};
+use crate::result::Error;
+use crate::utils::Id;
+
+// Macro generating the ChannelPosition enum
+macro_rules! channel_positions {
+    ($( ($variant:ident, $raw:ident, $short_name:literal, $name:literal) ),* $(,)?) => {
+        /// The position of an audio channel.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        pub enum ChannelPosition {
+            $(
+                #[doc = $name]
+                $variant,
+            )*
+            /// A position that is not known by this crate, such as an auxiliary or custom channel.
+            Other(u32),
+        }
+
+        impl ChannelPosition {
+            /// The raw representation of the position
+            pub fn as_raw(&self) -> spa_sys::spa_audio_channel {
+                match self {
+                    $(
+                        Self::$variant => spa_sys::$raw,
+                    )*
+                    Self::Other(raw) => *raw,
+                }
+            }
+
+            /// Create a `ChannelPosition` from a raw `spa_audio_channel`.
+            pub fn from_raw(raw: spa_sys::spa_audio_channel) -> Self {
+                match raw {
+                    $(
+                        spa_sys::$raw => Self::$variant,
+                    )*
+                    raw => Self::Other(raw),
+                }
+            }
+
+            /// The human-readable name of the position, such as `Front Left`.
+            ///
+            /// Returns `Unknown` for [`ChannelPosition::Other`] positions.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$variant => $name,
+                    )*
+                    Self::Other(_) => "Unknown",
+                }
+            }
+
+            /// The abbreviated name of the position, such as `FL`.
+            ///
+            /// Returns `UNK` for [`ChannelPosition::Other`] positions.
+            pub fn short_name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$variant => $short_name,
+                    )*
+                    Self::Other(_) => "UNK",
+                }
+            }
+        }
+
+        impl TryFrom<spa_sys::spa_audio_channel> for ChannelPosition {
+            type Error = spa_sys::spa_audio_channel;
+
+            /// Convert a raw `spa_audio_channel`, returning it back as error if it is not known by this crate.
+            fn try_from(raw: spa_sys::spa_audio_channel) -> Result<Self, Self::Error> {
+                match Self::from_raw(raw) {
+                    Self::Other(raw) => Err(raw),
+                    position => Ok(position),
+                }
+            }
+        }
+
+        impl fmt::Display for ChannelPosition {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    Self::Other(raw) => write!(f, "{}", raw),
+                    _ => f.write_str(self.short_name()),
+                }
+            }
+        }
+    };
+}
+
+channel_positions! {
+    // Variant, raw value, short name, name
+    (Unknown, SPA_AUDIO_CHANNEL_UNKNOWN, "UNK", "Unspecified"),
+    (NA, SPA_AUDIO_CHANNEL_NA, "NA", "N/A, silent"),
+    (Mono, SPA_AUDIO_CHANNEL_MONO, "MONO", "Mono stream"),
+    (FrontLeft, SPA_AUDIO_CHANNEL_FL, "FL", "Front Left"),
+    (FrontRight, SPA_AUDIO_CHANNEL_FR, "FR", "Front Right"),
+    (FrontCenter, SPA_AUDIO_CHANNEL_FC, "FC", "Front Center"),
+    (LowFrequency, SPA_AUDIO_CHANNEL_LFE, "LFE", "LFE 1"),
+    (SideLeft, SPA_AUDIO_CHANNEL_SL, "SL", "Side Left"),
+    (SideRight, SPA_AUDIO_CHANNEL_SR, "SR", "Side Right"),
+    (FrontLeftCenter, SPA_AUDIO_CHANNEL_FLC, "FLC", "Front Left Center"),
+    (FrontRightCenter, SPA_AUDIO_CHANNEL_FRC, "FRC", "Front Right Center"),
+    (RearCenter, SPA_AUDIO_CHANNEL_RC, "RC", "Rear Center"),
+    (RearLeft, SPA_AUDIO_CHANNEL_RL, "RL", "Rear Left"),
+    (RearRight, SPA_AUDIO_CHANNEL_RR, "RR", "Rear Right"),
+    (TopCenter, SPA_AUDIO_CHANNEL_TC, "TC", "Top Center"),
+    (TopFrontLeft, SPA_AUDIO_CHANNEL_TFL, "TFL", "Top Front Left"),
+    (TopFrontCenter, SPA_AUDIO_CHANNEL_TFC, "TFC", "Top Front Center"),
+    (TopFrontRight, SPA_AUDIO_CHANNEL_TFR, "TFR", "Top Front Right"),
+    (TopRearLeft, SPA_AUDIO_CHANNEL_TRL, "TRL", "Top Rear Left"),
+    (TopRearCenter, SPA_AUDIO_CHANNEL_TRC, "TRC", "Top Rear Center"),
+    (TopRearRight, SPA_AUDIO_CHANNEL_TRR, "TRR", "Top Rear Right"),
+    (RearLeftCenter, SPA_AUDIO_CHANNEL_RLC, "RLC", "Rear Left Center"),
+    (RearRightCenter, SPA_AUDIO_CHANNEL_RRC, "RRC", "Rear Right Center"),
+    (FrontLeftWide, SPA_AUDIO_CHANNEL_FLW, "FLW", "Front Left Wide"),
+    (FrontRightWide, SPA_AUDIO_CHANNEL_FRW, "FRW", "Front Right Wide"),
+    (LowFrequency2, SPA_AUDIO_CHANNEL_LFE2, "LFE2", "LFE 2"),
+    (FrontLeftHigh, SPA_AUDIO_CHANNEL_FLH, "FLH", "Front Left High"),
+    (FrontCenterHigh, SPA_AUDIO_CHANNEL_FCH, "FCH", "Front Center High"),
+    (FrontRightHigh, SPA_AUDIO_CHANNEL_FRH, "FRH", "Front Right High"),
+    (TopFrontLeftCenter, SPA_AUDIO_CHANNEL_TFLC, "TFLC", "Top Front Left Center"),
+    (TopFrontRightCenter, SPA_AUDIO_CHANNEL_TFRC, "TFRC", "Top Front Right Center"),
+    (TopSideLeft, SPA_AUDIO_CHANNEL_TSL, "TSL", "Top Side Left"),
+    (TopSideRight, SPA_AUDIO_CHANNEL_TSR, "TSR", "Top Side Right"),
+    (LeftLowFrequency, SPA_AUDIO_CHANNEL_LLFE, "LLFE", "Left LFE"),
+    (RightLowFrequency, SPA_AUDIO_CHANNEL_RLFE, "RLFE", "Right LFE"),
+    (BottomCenter, SPA_AUDIO_CHANNEL_BC, "BC", "Bottom Center"),
+    (BottomLeftCenter, SPA_AUDIO_CHANNEL_BLC, "BLC", "Bottom Left Center"),
+    (BottomRightCenter, SPA_AUDIO_CHANNEL_BRC, "BRC", "Bottom Right Center"),
+}
+
+// Macro generating the AudioFormat enum
+macro_rules! audio_formats {
+    ($( ($variant:ident, $raw:ident, $name:literal) ),* $(,)?) => {
+        /// The format of audio samples.
+        ///
+        /// Formats ending in `p` are planar, with one plane per channel, and use the native endianness.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        #[allow(missing_docs)]
+        pub enum AudioFormat {
+            $(
+                $variant,
+            )*
+        }
+
+        impl AudioFormat {
+            /// The raw representation of the format
+            pub fn as_raw(&self) -> spa_sys::spa_audio_format {
+                match self {
+                    $(
+                        Self::$variant => spa_sys::$raw,
+                    )*
+                }
+            }
+
+            /// Create an `AudioFormat` from a raw `spa_audio_format`.
+            ///
+            /// Formats that are not known by this crate are mapped to [`AudioFormat::Unknown`].
+            pub fn from_raw(raw: spa_sys::spa_audio_format) -> Self {
+                match raw {
+                    $(
+                        spa_sys::$raw => Self::$variant,
+                    )*
+                    _ => Self::Unknown,
+                }
+            }
+
+            /// The canonical name of the format, as used in PipeWire configuration files, such as `S16LE`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$variant => $name,
+                    )*
+                }
+            }
+        }
+
+        impl TryFrom<spa_sys::spa_audio_format> for AudioFormat {
+            type Error = spa_sys::spa_audio_format;
+
+            /// Convert a raw `spa_audio_format`, returning it back as error if it is not known by this crate.
+            fn try_from(raw: spa_sys::spa_audio_format) -> Result<Self, Self::Error> {
+                match raw {
+                    $(
+                        spa_sys::$raw => Ok(Self::$variant),
+                    )*
+                    _ => Err(raw),
+                }
+            }
+        }
+
+        impl FromStr for AudioFormat {
+            type Err = ParseNameError;
+
+            /// Parse a format from its canonical name, such as `F32LE`, ignoring case.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(
+                    if s.eq_ignore_ascii_case($name) {
+                        return Ok(Self::$variant);
+                    }
+                )*
+
+                Err(ParseNameError::new(s))
+            }
+        }
+
+        impl fmt::Display for AudioFormat {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+    };
+}
+
+audio_formats! {
+    // Variant, raw value, name
+    (Unknown, SPA_AUDIO_FORMAT_UNKNOWN, "UNKNOWN"),
+    (Encoded, SPA_AUDIO_FORMAT_ENCODED, "ENCODED"),
+    (S8, SPA_AUDIO_FORMAT_S8, "S8"),
+    (U8, SPA_AUDIO_FORMAT_U8, "U8"),
+    (S16le, SPA_AUDIO_FORMAT_S16_LE, "S16LE"),
+    (S16be, SPA_AUDIO_FORMAT_S16_BE, "S16BE"),
+    (U16le, SPA_AUDIO_FORMAT_U16_LE, "U16LE"),
+    (U16be, SPA_AUDIO_FORMAT_U16_BE, "U16BE"),
+    (S24_32le, SPA_AUDIO_FORMAT_S24_32_LE, "S24_32LE"),
+    (S24_32be, SPA_AUDIO_FORMAT_S24_32_BE, "S24_32BE"),
+    (U24_32le, SPA_AUDIO_FORMAT_U24_32_LE, "U24_32LE"),
+    (U24_32be, SPA_AUDIO_FORMAT_U24_32_BE, "U24_32BE"),
+    (S32le, SPA_AUDIO_FORMAT_S32_LE, "S32LE"),
+    (S32be, SPA_AUDIO_FORMAT_S32_BE, "S32BE"),
+    (U32le, SPA_AUDIO_FORMAT_U32_LE, "U32LE"),
+    (U32be, SPA_AUDIO_FORMAT_U32_BE, "U32BE"),
+    (S24le, SPA_AUDIO_FORMAT_S24_LE, "S24LE"),
+    (S24be, SPA_AUDIO_FORMAT_S24_BE, "S24BE"),
+    (U24le, SPA_AUDIO_FORMAT_U24_LE, "U24LE"),
+    (U24be, SPA_AUDIO_FORMAT_U24_BE, "U24BE"),
+    (S20le, SPA_AUDIO_FORMAT_S20_LE, "S20LE"),
+    (S20be, SPA_AUDIO_FORMAT_S20_BE, "S20BE"),
+    (U20le, SPA_AUDIO_FORMAT_U20_LE, "U20LE"),
+    (U20be, SPA_AUDIO_FORMAT_U20_BE, "U20BE"),
+    (S18le, SPA_AUDIO_FORMAT_S18_LE, "S18LE"),
+    (S18be, SPA_AUDIO_FORMAT_S18_BE, "S18BE"),
+    (U18le, SPA_AUDIO_FORMAT_U18_LE, "U18LE"),
+    (U18be, SPA_AUDIO_FORMAT_U18_BE, "U18BE"),
+    (F32le, SPA_AUDIO_FORMAT_F32_LE, "F32LE"),
+    (F32be, SPA_AUDIO_FORMAT_F32_BE, "F32BE"),
+    (F64le, SPA_AUDIO_FORMAT_F64_LE, "F64LE"),
+    (F64be, SPA_AUDIO_FORMAT_F64_BE, "F64BE"),
+    (U8p, SPA_AUDIO_FORMAT_U8P, "U8P"),
+    (S16p, SPA_AUDIO_FORMAT_S16P, "S16P"),
+    (S24_32p, SPA_AUDIO_FORMAT_S24_32P, "S24_32P"),
+    (S32p, SPA_AUDIO_FORMAT_S32P, "S32P"),
+    (S24p, SPA_AUDIO_FORMAT_S24P, "S24P"),
+    (F32p, SPA_AUDIO_FORMAT_F32P, "F32P"),
+    (F64p, SPA_AUDIO_FORMAT_F64P, "F64P"),
+    (S8p, SPA_AUDIO_FORMAT_S8P, "S8P"),
+}
+
+impl AudioFormat {
+    /// The size of a single sample in bytes.
+    ///
+    /// Returns 0 for [`AudioFormat::Unknown`] and [`AudioFormat::Encoded`], which have no fixed sample size.
+    pub fn sample_size(&self) -> usize {
+        match self {
+            Self::Unknown | Self::Encoded => 0,
+            Self::S8 | Self::U8 | Self::U8p | Self::S8p => 1,
+            Self::S16le | Self::S16be | Self::U16le | Self::U16be | Self::S16p => 2,
+            Self::S24le
+            | Self::S24be
+            | Self::U24le
+            | Self::U24be
+            | Self::S20le
+            | Self::S20be
+            | Self::U20le
+            | Self::U20be
+            | Self::S18le
+            | Self::S18be
+            | Self::U18le
+            | Self::U18be
+            | Self::S24p => 3,
+            Self::S24_32le
+            | Self::S24_32be
+            | Self::U24_32le
+            | Self::U24_32be
+            | Self::S32le
+            | Self::S32be
+            | Self::U32le
+            | Self::U32be
+            | Self::F32le
+            | Self::F32be
+            | Self::S24_32p
+            | Self::S32p
+            | Self::F32p => 4,
+            Self::F64le | Self::F64be | Self::F64p => 8,
+        }
+    }
+
+    /// Whether the samples are floating point numbers.
+    pub fn is_float(&self) -> bool {
+        matches!(
+            self,
+            Self::F32le | Self::F32be | Self::F64le | Self::F64be | Self::F32p | Self::F64p
+        )
+    }
+
+    /// Whether the samples are signed, which includes floating point formats.
+    pub fn is_signed(&self) -> bool {
+        self.is_float()
+            || matches!(
+                self,
+                Self::S8
+                    | Self::S16le
+                    | Self::S16be
+                    | Self::S24_32le
+                    | Self::S24_32be
+                    | Self::S32le
+                    | Self::S32be
+                    | Self::S24le
+                    | Self::S24be
+                    | Self::S20le
+                    | Self::S20be
+                    | Self::S18le
+                    | Self::S18be
+                    | Self::S16p
+                    | Self::S24_32p
+                    | Self::S32p
+                    | Self::S24p
+                    | Self::S8p
+            )
+    }
+
+    /// Whether the samples are stored in little endian byte order.
+    ///
+    /// Planar formats use the native byte order.
+    /// Returns `false` for formats with single byte samples, which have no byte order.
+    pub fn is_little_endian(&self) -> bool {
+        match self {
+            Self::S16le
+            | Self::U16le
+            | Self::S24_32le
+            | Self::U24_32le
+            | Self::S32le
+            | Self::U32le
+            | Self::S24le
+            | Self::U24le
+            | Self::S20le
+            | Self::U20le
+            | Self::S18le
+            | Self::U18le
+            | Self::F32le
+            | Self::F64le => true,
+            Self::S16p | Self::S24_32p | Self::S32p | Self::S24p | Self::F32p | Self::F64p => {
+                cfg!(target_endian = "little")
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the samples are stored in big endian byte order.
+    ///
+    /// Planar formats use the native byte order.
+    /// Returns `false` for formats with single byte samples, which have no byte order.
+    pub fn is_big_endian(&self) -> bool {
+        match self {
+            Self::S16be
+            | Self::U16be
+            | Self::S24_32be
+            | Self::U24_32be
+            | Self::S32be
+            | Self::U32be
+            | Self::S24be
+            | Self::U24be
+            | Self::S20be
+            | Self::U20be
+            | Self::S18be
+            | Self::U18be
+            | Self::F32be
+            | Self::F64be => true,
+            Self::S16p | Self::S24_32p | Self::S32p | Self::S24p | Self::F32p | Self::F64p => {
+                cfg!(target_endian = "big")
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the samples are stored in the native byte order of the target.
+    ///
+    /// Formats without a byte order, such as those with single byte samples, are considered native.
+    pub fn is_native_endian(&self) -> bool {
+        if cfg!(target_endian = "little") {
+            !self.is_big_endian()
+        } else {
+            !self.is_little_endian()
+        }
+    }
+
+    /// Get the equivalent format using the native byte order of the target.
+    ///
+    /// Formats without an explicit byte order are returned unchanged.
+    pub fn to_native_endian(self) -> Self {
+        if cfg!(target_endian = "little") {
+            self.to_little_endian()
+        } else {
+            self.to_big_endian()
+        }
+    }
+
+    /// Get the equivalent format using the little endian byte order.
+    ///
+    /// Formats without an explicit byte order are returned unchanged.
+    pub fn to_little_endian(self) -> Self {
+        self.endian_variants().map_or(self, |(le, _)| le)
+    }
+
+    /// Get the equivalent format using the big endian byte order.
+    ///
+    /// Formats without an explicit byte order are returned unchanged.
+    pub fn to_big_endian(self) -> Self {
+        self.endian_variants().map_or(self, |(_, be)| be)
+    }
+
+    /// The little and big endian variants of interleaved formats with multi-byte samples.
+    fn endian_variants(&self) -> Option<(Self, Self)> {
+        match self {
+            Self::S16le | Self::S16be => Some((Self::S16le, Self::S16be)),
+            Self::U16le | Self::U16be => Some((Self::U16le, Self::U16be)),
+            Self::S24_32le | Self::S24_32be => Some((Self::S24_32le, Self::S24_32be)),
+            Self::U24_32le | Self::U24_32be => Some((Self::U24_32le, Self::U24_32be)),
+            Self::S32le | Self::S32be => Some((Self::S32le, Self::S32be)),
+            Self::U32le | Self::U32be => Some((Self::U32le, Self::U32be)),
+            Self::S24le | Self::S24be => Some((Self::S24le, Self::S24be)),
+            Self::U24le | Self::U24be => Some((Self::U24le, Self::U24be)),
+            Self::S20le | Self::S20be => Some((Self::S20le, Self::S20be)),
+            Self::U20le | Self::U20be => Some((Self::U20le, Self::U20be)),
+            Self::S18le | Self::S18be => Some((Self::S18le, Self::S18be)),
+            Self::U18le | Self::U18be => Some((Self::U18le, Self::U18be)),
+            Self::F32le | Self::F32be => Some((Self::F32le, Self::F32be)),
+            Self::F64le | Self::F64be => Some((Self::F64le, Self::F64be)),
+            _ => None,
+        }
+    }
+}
+
+/// The maximum number of channels in an [`AudioInfoRaw`].
+pub const MAX_CHANNELS: usize = spa_sys::SPA_AUDIO_MAX_CHANNELS as usize;
+
+/// A transparent wrapper around a `spa_sys::spa_audio_info_raw`, describing a raw audio format.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct AudioInfoRaw(spa_sys::spa_audio_info_raw);
+
+impl AudioInfoRaw {
+    /// Create a new `AudioInfoRaw` with an unknown format and no channels.
+    pub fn new() -> Self {
+        Self(spa_sys::spa_audio_info_raw {
+            format: spa_sys::SPA_AUDIO_FORMAT_UNKNOWN,
+            flags: 0,
+            rate: 0,
+            channels: 0,
+            position: [spa_sys::SPA_AUDIO_CHANNEL_UNKNOWN; MAX_CHANNELS],
+        })
+    }
+
+    /// Create an `AudioInfoRaw` from a raw `spa_audio_info_raw`.
+    pub fn from_raw(raw: spa_sys::spa_audio_info_raw) -> Self {
+        Self(raw)
+    }
+
+    /// The raw representation of the audio info.
+    pub fn as_raw(&self) -> spa_sys::spa_audio_info_raw {
+        self.0
+    }
+
+    /// Get the format of the samples.
+    pub fn format(&self) -> AudioFormat {
+        AudioFormat::from_raw(self.0.format)
+    }
+
+    /// Set the format of the samples.
+    pub fn set_format(&mut self, format: AudioFormat) {
+        self.0.format = format.as_raw();
+    }
+
+    /// Get the raw `SPA_AUDIO_FLAG_*` flags.
+    pub fn flags(&self) -> u32 {
+        self.0.flags
+    }
+
+    /// Set the raw `SPA_AUDIO_FLAG_*` flags.
+    pub fn set_flags(&mut self, flags: u32) {
+        self.0.flags = flags;
+    }
+
+    /// Get the sample rate.
+    pub fn rate(&self) -> u32 {
+        self.0.rate
+    }
+
+    /// Set the sample rate.
+    pub fn set_rate(&mut self, rate: u32) {
+        self.0.rate = rate;
+    }
+
+    /// Get the number of channels.
+    pub fn channels(&self) -> u32 {
+        self.0.channels
+    }
+
+    /// Set the number of channels.
+    pub fn set_channels(&mut self, channels: u32) {
+        self.0.channels = channels;
+    }
+
+    /// Get the position of each channel.
+    pub fn position(&self) -> Vec<ChannelPosition> {
+        let channels = (self.0.channels as usize).min(MAX_CHANNELS);
+        self.0.position[..channels]
+            .iter()
+            .map(|raw| ChannelPosition::from_raw(*raw))
+            .collect()
+    }
+
+    /// Set the position of each channel, also updating the number of channels.
+    ///
+    /// # Panics
+    /// If there are more than [`MAX_CHANNELS`] positions.
+    pub fn set_position(&mut self, position: &[ChannelPosition]) {
+        assert!(
+            position.len() <= MAX_CHANNELS,
+            "Too many channel positions: {}",
+            position.len()
+        );
+
+        for (raw, pos) in self.0.position.iter_mut().zip(position) {
+            *raw = pos.as_raw();
+        }
+        self.0.channels = position.len() as u32;
+    }
+
+    /// The size of a frame in bytes, made of one sample for each channel.
+    ///
+    /// For planar formats, this is the total size of a frame across all planes.
+    pub fn bytes_per_frame(&self) -> usize {
+        self.format().sample_size() * self.channels() as usize
+    }
+
+    /// Describe the channel layout as a space-separated list of channel short names, such as `FL FR LFE`.
+    pub fn channels_description(&self) -> String {
+        self.position()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Write the audio info to `builder` as a raw audio `SPA_TYPE_OBJECT_Format` object,
+    /// whose id is the parameter `id`, usually [`ParamType::ENUM_FORMAT`] or [`ParamType::FORMAT`].
+    ///
+    /// Like the C `spa_format_audio_raw_build()`, the format, rate and channels are only written
+    /// if they are set, and the channel positions only if the `SPA_AUDIO_FLAG_UNPOSITIONED` flag is not set.
+    ///
+    /// # Errors
+    /// Returns an `ENOSPC` error if the pod could not be written to the builder.
+    pub fn to_pod(&self, builder: &mut PodBuilder, id: ParamType) -> Result<(), Error> {
+        self.to_pod_with_media(builder, id, MediaType::Audio, MediaSubtype::Raw)
+    }
+
+    /// Same as [`to_pod`](Self::to_pod), with a custom media type and subtype.
+    pub(crate) fn to_pod_with_media(
+        &self,
+        builder: &mut PodBuilder,
+        id: ParamType,
+        media_type: MediaType,
+        media_subtype: MediaSubtype,
+    ) -> Result<(), Error> {
+        let frame = builder.begin_object(spa_sys::SPA_TYPE_OBJECT_Format, id.as_raw());
+
+        builder.push_property(spa_sys::SPA_FORMAT_mediaType, PropertyFlags::empty());
+        builder.push_id(Id(media_type.as_raw()))?;
+        builder.push_property(spa_sys::SPA_FORMAT_mediaSubtype, PropertyFlags::empty());
+        builder.push_id(Id(media_subtype.as_raw()))?;
+
+        if self.0.format != spa_sys::SPA_AUDIO_FORMAT_UNKNOWN {
+            builder.push_property(spa_sys::SPA_FORMAT_AUDIO_format, PropertyFlags::empty());
+            builder.push_id(Id(self.0.format))?;
+        }
+        if self.0.rate != 0 {
+            builder.push_property(spa_sys::SPA_FORMAT_AUDIO_rate, PropertyFlags::empty());
+            builder.push_int(self.0.rate as i32)?;
+        }
+        if self.0.channels != 0 {
+            builder.push_property(spa_sys::SPA_FORMAT_AUDIO_channels, PropertyFlags::empty());
+            builder.push_int(self.0.channels as i32)?;
+
+            if self.0.flags & spa_sys::SPA_AUDIO_FLAG_UNPOSITIONED == 0 {
+                let channels = (self.0.channels as usize).min(MAX_CHANNELS);
+                builder.push_property(spa_sys::SPA_FORMAT_AUDIO_position, PropertyFlags::empty());
+                let mut array = builder.begin_array();
+                for pos in &self.0.position[..channels] {
+                    array.push_element(Id(*pos))?;
+                }
+                array.finish();
+            }
+        }
+
+        builder.end_object(frame);
+        Ok(())
+    }
+
+    /// Parse the audio info from a raw audio `SPA_TYPE_OBJECT_Format` object,
+    /// such as the format received in a `param_changed` stream callback.
+    ///
+    /// The format must be fixated: properties whose value is a choice are rejected.
+    ///
+    /// # Errors
+    /// Returns an `EINVAL` error if `pod` is not a raw audio format object or is malformed.
+    pub fn from_pod(pod: &Pod) -> Result<Self, Error> {
+        let invalid = |_| Error::new(libc::EINVAL);
+
+        let mut object = PodParser::new(pod).enter_object().map_err(invalid)?;
+        if object.type_() != spa_sys::SPA_TYPE_OBJECT_Format {
+            return Err(Error::new(libc::EINVAL));
+        }
+
+        let mut info = Self::new();
+        let mut media_type = None;
+        let mut media_subtype = None;
+
+        while let Some((key, _flags, mut value)) = object.next_property().map_err(invalid)? {
+            match key {
+                spa_sys::SPA_FORMAT_mediaType => {
+                    media_type = Some(MediaType::from_raw(value.get_id().map_err(invalid)?.0))
+                }
+                spa_sys::SPA_FORMAT_mediaSubtype => {
+                    media_subtype = Some(MediaSubtype::from_raw(value.get_id().map_err(invalid)?.0))
+                }
+                spa_sys::SPA_FORMAT_AUDIO_format => {
+                    info.0.format = value.get_id().map_err(invalid)?.0
+                }
+                spa_sys::SPA_FORMAT_AUDIO_flags => {
+                    info.0.flags = value.get_int().map_err(invalid)? as u32
+                }
+                spa_sys::SPA_FORMAT_AUDIO_rate => {
+                    info.0.rate = value.get_int().map_err(invalid)? as u32
+                }
+                spa_sys::SPA_FORMAT_AUDIO_channels => {
+                    info.0.channels = value.get_int().map_err(invalid)? as u32
+                }
+                spa_sys::SPA_FORMAT_AUDIO_position => {
+                    let position: Vec<Id> = value.get_array().map_err(invalid)?;
+                    for (raw, pos) in info.0.position.iter_mut().zip(position) {
+                        *raw = pos.0;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if media_type != Some(MediaType::Audio) || media_subtype != Some(MediaSubtype::Raw) {
+            return Err(Error::new(libc::EINVAL));
+        }
+
+        Ok(info)
+    }
+}
+
+impl Default for AudioInfoRaw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for AudioInfoRaw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioInfoRaw")
+            .field("format", &self.format())
+            .field("flags", &self.flags())
+            .field("rate", &self.rate())
+            .field("channels", &self.channels())
+            .field("position", &self.position())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_position_raw() {
+        assert_eq!(
+            ChannelPosition::FrontLeft.as_raw(),
+            spa_sys::SPA_AUDIO_CHANNEL_FL
+        );
+        assert_eq!(
+            ChannelPosition::from_raw(spa_sys::SPA_AUDIO_CHANNEL_LFE),
+            ChannelPosition::LowFrequency
+        );
+        assert_eq!(
+            ChannelPosition::from_raw(0x10000),
+            ChannelPosition::Other(0x10000)
+        );
+        assert_eq!(ChannelPosition::Other(0x10000).as_raw(), 0x10000);
+    }
+
+    #[test]
+    fn channel_position_names() {
+        assert_eq!(ChannelPosition::FrontLeft.name(), "Front Left");
+        assert_eq!(ChannelPosition::FrontLeft.short_name(), "FL");
+        assert_eq!(ChannelPosition::LowFrequency.name(), "LFE 1");
+        assert_eq!(ChannelPosition::LowFrequency.short_name(), "LFE");
+        assert_eq!(ChannelPosition::Other(0x10000).short_name(), "UNK");
+    }
+
+    #[test]
+    fn audio_format_raw() {
+        assert_eq!(
+            AudioFormat::S16le.as_raw(),
+            spa_sys::SPA_AUDIO_FORMAT_S16_LE
+        );
+        assert_eq!(
+            AudioFormat::from_raw(spa_sys::SPA_AUDIO_FORMAT_F32P),
+            AudioFormat::F32p
+        );
+    }
+
+    #[test]
+    fn try_from_raw() {
+        assert_eq!(
+            AudioFormat::try_from(spa_sys::SPA_AUDIO_FORMAT_S24_LE),
+            Ok(AudioFormat::S24le)
+        );
+        assert_eq!(AudioFormat::try_from(u32::MAX), Err(u32::MAX));
+        assert_eq!(
+            ChannelPosition::try_from(spa_sys::SPA_AUDIO_CHANNEL_MONO),
+            Ok(ChannelPosition::Mono)
+        );
+        assert_eq!(ChannelPosition::try_from(0x10000), Err(0x10000));
+    }
+
+    #[test]
+    fn audio_format_predicates() {
+        assert!(AudioFormat::F32le.is_float());
+        assert!(AudioFormat::F32le.is_signed());
+        assert!(AudioFormat::F32le.is_little_endian());
+        assert!(!AudioFormat::F32le.is_big_endian());
+
+        assert!(!AudioFormat::U16be.is_float());
+        assert!(!AudioFormat::U16be.is_signed());
+        assert!(AudioFormat::U16be.is_big_endian());
+
+        assert!(AudioFormat::S8.is_signed());
+        assert!(!AudioFormat::S8.is_little_endian());
+        assert!(!AudioFormat::S8.is_big_endian());
+
+        assert_eq!(
+            AudioFormat::S16p.is_little_endian(),
+            cfg!(target_endian = "little")
+        );
+    }
+
+    #[test]
+    fn bytes_per_frame() {
+        assert_eq!(AudioFormat::U8.sample_size(), 1);
+        assert_eq!(AudioFormat::S24le.sample_size(), 3);
+        assert_eq!(AudioFormat::F64be.sample_size(), 8);
+
+        let mut info = AudioInfoRaw::new();
+        assert_eq!(info.bytes_per_frame(), 0);
+
+        info.set_format(AudioFormat::S24_32le);
+        info.set_channels(6);
+        assert_eq!(info.bytes_per_frame(), 24);
+    }
+
+    #[test]
+    fn channels_description() {
+        let mut info = AudioInfoRaw::new();
+        assert_eq!(info.channels_description(), "");
+
+        info.set_position(&[
+            ChannelPosition::FrontLeft,
+            ChannelPosition::FrontRight,
+            ChannelPosition::LowFrequency,
+            ChannelPosition::SideLeft,
+            ChannelPosition::SideRight,
+        ]);
+        assert_eq!(info.channels(), 5);
+        assert_eq!(info.channels_description(), "FL FR LFE SL SR");
+    }
+
+    #[test]
+    fn audio_format_endian() {
+        assert_eq!(AudioFormat::S16be.to_little_endian(), AudioFormat::S16le);
+        assert_eq!(AudioFormat::F32le.to_big_endian(), AudioFormat::F32be);
+        assert_eq!(
+            AudioFormat::S24_32le.to_little_endian(),
+            AudioFormat::S24_32le
+        );
+        assert_eq!(AudioFormat::F32p.to_big_endian(), AudioFormat::F32p);
+        assert_eq!(AudioFormat::U8.to_little_endian(), AudioFormat::U8);
+
+        assert!(AudioFormat::U32le.to_native_endian().is_native_endian());
+        assert!(AudioFormat::U32be.to_native_endian().is_native_endian());
+        assert!(AudioFormat::S8.is_native_endian());
+        assert!(AudioFormat::S16p.is_native_endian());
+        #[cfg(target_endian = "little")]
+        assert!(!AudioFormat::S16be.is_native_endian());
+        #[cfg(target_endian = "big")]
+        assert!(!AudioFormat::S16le.is_native_endian());
+    }
+
+    #[test]
+    fn audio_format_name() {
+        assert_eq!(AudioFormat::S24_32le.to_string(), "S24_32LE");
+        assert_eq!("F32P".parse(), Ok(AudioFormat::F32p));
+        assert_eq!("S16BE".parse(), Ok(AudioFormat::S16be));
+        assert_eq!("s16le".parse(), Ok(AudioFormat::S16le));
+        assert_eq!("S16".parse::<AudioFormat>().unwrap_err().name(), "S16");
+    }
+
+    #[test]
+    fn audio_info_raw_pod() {
+        let mut info = AudioInfoRaw::new();
+        info.set_format(AudioFormat::F32le);
+        info.set_rate(48000);
+        info.set_position(&[ChannelPosition::FrontLeft, ChannelPosition::FrontRight]);
+
+        let mut builder = PodBuilder::new();
+        info.to_pod(&mut builder, ParamType::ENUM_FORMAT).unwrap();
+        let pod = builder.build_owned();
+
+        let parsed = AudioInfoRaw::from_pod(&pod).unwrap();
+        assert_eq!(parsed.format(), AudioFormat::F32le);
+        assert_eq!(parsed.rate(), 48000);
+        assert_eq!(parsed.channels(), 2);
+        assert_eq!(
+            parsed.position(),
+            vec![ChannelPosition::FrontLeft, ChannelPosition::FrontRight]
+        );
+
+        let mut builder = PodBuilder::new();
+        builder.push_int(1).unwrap();
+        assert!(AudioInfoRaw::from_pod(&builder.build_owned()).is_err());
+    }
+}
@@ -0,0 +1,474 @@
+//! Format parameters, common to all media types.
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use cookie_factory::GenError;
+
+use super::{
+    audio::{AudioFormat, AudioInfoRaw, ChannelPosition, MAX_CHANNELS},
+    ParamType, ParseNameError,
+};
+use crate::pod::{builder::PodBuilder, Object, PodOwned, Property, PropertyFlags, Value};
+use crate::result::Error;
+use crate::utils::Id;
+
+// Macro generating an enum mapping variants to raw SPA constants
+macro_rules! media_enum {
+    ($(#[$attr:meta])* $name:ident, $raw_type:ty, $( ($variant:ident, $raw:ident, $str:literal) ),* $(,)?) => {
+        $(#[$attr])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        #[allow(missing_docs)]
+        pub enum $name {
+            $(
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// The raw representation of the value
+            pub fn as_raw(&self) -> $raw_type {
+                match self {
+                    $(
+                        Self::$variant => spa_sys::$raw,
+                    )*
+                }
+            }
+
+            /// Create a value from its raw representation.
+            ///
+            /// Values that are not known by this crate are mapped to `Unknown`.
+            pub fn from_raw(raw: $raw_type) -> Self {
+                match raw {
+                    $(
+                        spa_sys::$raw => Self::$variant,
+                    )*
+                    _ => Self::Unknown,
+                }
+            }
+
+            /// The name of the value, as used by SPA, such as `audio` or `raw`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$variant => $str,
+                    )*
+                }
+            }
+        }
+
+        impl TryFrom<$raw_type> for $name {
+            type Error = $raw_type;
+
+            /// Convert a raw value, returning it back as error if it is not known by this crate.
+            fn try_from(raw: $raw_type) -> Result<Self, Self::Error> {
+                match raw {
+                    $(
+                        spa_sys::$raw => Ok(Self::$variant),
+                    )*
+                    _ => Err(raw),
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseNameError;
+
+            /// Parse a value from its name, ignoring case.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(
+                    if s.eq_ignore_ascii_case($str) {
+                        return Ok(Self::$variant);
+                    }
+                )*
+
+                Err(ParseNameError::new(s))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+    };
+}
+
+media_enum! {
+    /// The media type of a format.
+    MediaType,
+    spa_sys::spa_media_type,
+    (Unknown, SPA_MEDIA_TYPE_unknown, "unknown"),
+    (Audio, SPA_MEDIA_TYPE_audio, "audio"),
+    (Video, SPA_MEDIA_TYPE_video, "video"),
+    (Image, SPA_MEDIA_TYPE_image, "image"),
+    (Binary, SPA_MEDIA_TYPE_binary, "binary"),
+    (Stream, SPA_MEDIA_TYPE_stream, "stream"),
+    (Application, SPA_MEDIA_TYPE_application, "application"),
+}
+
+media_enum! {
+    /// The media subtype of a format, refining its [`MediaType`].
+    MediaSubtype,
+    spa_sys::spa_media_subtype,
+    (Unknown, SPA_MEDIA_SUBTYPE_unknown, "unknown"),
+    (Raw, SPA_MEDIA_SUBTYPE_raw, "raw"),
+    (Dsp, SPA_MEDIA_SUBTYPE_dsp, "dsp"),
+    (Iec958, SPA_MEDIA_SUBTYPE_iec958, "iec958"),
+    (Dsd, SPA_MEDIA_SUBTYPE_dsd, "dsd"),
+    (Mp3, SPA_MEDIA_SUBTYPE_mp3, "mp3"),
+    (Aac, SPA_MEDIA_SUBTYPE_aac, "aac"),
+    (Vorbis, SPA_MEDIA_SUBTYPE_vorbis, "vorbis"),
+    (Wma, SPA_MEDIA_SUBTYPE_wma, "wma"),
+    (Ra, SPA_MEDIA_SUBTYPE_ra, "ra"),
+    (Sbc, SPA_MEDIA_SUBTYPE_sbc, "sbc"),
+    (Adpcm, SPA_MEDIA_SUBTYPE_adpcm, "adpcm"),
+    (G723, SPA_MEDIA_SUBTYPE_g723, "g723"),
+    (G726, SPA_MEDIA_SUBTYPE_g726, "g726"),
+    (G729, SPA_MEDIA_SUBTYPE_g729, "g729"),
+    (Amr, SPA_MEDIA_SUBTYPE_amr, "amr"),
+    (Gsm, SPA_MEDIA_SUBTYPE_gsm, "gsm"),
+    (H264, SPA_MEDIA_SUBTYPE_h264, "h264"),
+    (Mjpg, SPA_MEDIA_SUBTYPE_mjpg, "mjpg"),
+    (Dv, SPA_MEDIA_SUBTYPE_dv, "dv"),
+    (Mpegts, SPA_MEDIA_SUBTYPE_mpegts, "mpegts"),
+    (H263, SPA_MEDIA_SUBTYPE_h263, "h263"),
+    (Mpeg1, SPA_MEDIA_SUBTYPE_mpeg1, "mpeg1"),
+    (Mpeg2, SPA_MEDIA_SUBTYPE_mpeg2, "mpeg2"),
+    (Mpeg4, SPA_MEDIA_SUBTYPE_mpeg4, "mpeg4"),
+    (Xvid, SPA_MEDIA_SUBTYPE_xvid, "xvid"),
+    (Vc1, SPA_MEDIA_SUBTYPE_vc1, "vc1"),
+    (Vp8, SPA_MEDIA_SUBTYPE_vp8, "vp8"),
+    (Vp9, SPA_MEDIA_SUBTYPE_vp9, "vp9"),
+    (Bayer, SPA_MEDIA_SUBTYPE_bayer, "bayer"),
+    (Jpeg, SPA_MEDIA_SUBTYPE_jpeg, "jpeg"),
+    (Midi, SPA_MEDIA_SUBTYPE_midi, "midi"),
+    (Control, SPA_MEDIA_SUBTYPE_control, "control"),
+}
+
+/// A builder for format filter pods.
+///
+/// Format filters can be passed to methods enumerating formats, such as `enum_params`
+/// with the `SPA_PARAM_EnumFormat` id, to only get the formats matching all the requirements
+/// of the filter.
+///
+/// # Examples
+/// ```rust
+/// use libspa::param::{audio::AudioFormat, format::{FormatFilter, MediaSubtype, MediaType}};
+/// use libspa::pod::builder::PodBuilder;
+///
+/// let mut builder = PodBuilder::new();
+/// FormatFilter::new()
+///     .require_media_type(MediaType::Audio)
+///     .require_media_subtype(MediaSubtype::Raw)
+///     .require_audio_format(AudioFormat::F32le)
+///     .require_rate(48000)
+///     .build(&mut builder)
+///     .unwrap();
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FormatFilter {
+    media_type: Option<MediaType>,
+    media_subtype: Option<MediaSubtype>,
+    audio_format: Option<AudioFormat>,
+    rate: Option<u32>,
+    channels: Option<u32>,
+}
+
+impl FormatFilter {
+    /// Create a new filter, without any requirement.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match formats with the given media type.
+    #[must_use]
+    pub fn require_media_type(mut self, media_type: MediaType) -> Self {
+        self.media_type = Some(media_type);
+        self
+    }
+
+    /// Only match formats with the given media subtype.
+    #[must_use]
+    pub fn require_media_subtype(mut self, media_subtype: MediaSubtype) -> Self {
+        self.media_subtype = Some(media_subtype);
+        self
+    }
+
+    /// Only match audio formats with the given sample format.
+    #[must_use]
+    pub fn require_audio_format(mut self, format: AudioFormat) -> Self {
+        self.audio_format = Some(format);
+        self
+    }
+
+    /// Only match audio formats with the given sample rate.
+    #[must_use]
+    pub fn require_rate(mut self, rate: u32) -> Self {
+        self.rate = Some(rate);
+        self
+    }
+
+    /// Only match audio formats with the given number of channels.
+    #[must_use]
+    pub fn require_channels(mut self, channels: u32) -> Self {
+        self.channels = Some(channels);
+        self
+    }
+
+    /// Create the [`Value`] of the filter, a `SPA_TYPE_OBJECT_Format` object.
+    pub fn to_value(&self) -> Value {
+        let ids = [
+            (
+                spa_sys::SPA_FORMAT_mediaType,
+                self.media_type.map(|t| t.as_raw()),
+            ),
+            (
+                spa_sys::SPA_FORMAT_mediaSubtype,
+                self.media_subtype.map(|t| t.as_raw()),
+            ),
+            (
+                spa_sys::SPA_FORMAT_AUDIO_format,
+                self.audio_format.map(|f| f.as_raw()),
+            ),
+        ];
+        let ints = [
+            (spa_sys::SPA_FORMAT_AUDIO_rate, self.rate),
+            (spa_sys::SPA_FORMAT_AUDIO_channels, self.channels),
+        ];
+
+        let ids = ids
+            .iter()
+            .filter_map(|(key, value)| value.map(|v| (*key, Value::Id(Id(v)))));
+        let ints = ints
+            .iter()
+            .filter_map(|(key, value)| value.map(|v| (*key, Value::Int(v as i32))));
+
+        Value::Object(Object {
+            type_: spa_sys::SPA_TYPE_OBJECT_Format,
+            id: spa_sys::SPA_PARAM_EnumFormat,
+            properties: ids
+                .chain(ints)
+                .map(|(key, value)| Property {
+                    key,
+                    flags: PropertyFlags::empty(),
+                    value,
+                })
+                .collect(),
+        })
+    }
+
+    /// Write the filter pod to `builder`.
+    pub fn build(&self, builder: &mut PodBuilder) -> Result<(), GenError> {
+        builder.push_pod(&self.to_value())
+    }
+}
+
+/// Builder for audio `SPA_TYPE_OBJECT_Format` pods, such as the formats passed when connecting a stream.
+///
+/// The pod is written by [`AudioInfoRaw::to_pod`](super::audio::AudioInfoRaw::to_pod),
+/// with the media type and subtype of the builder.
+/// Properties that are not set are not written to the format.
+///
+/// # Examples
+/// ```
+/// use libspa::param::{
+///     audio::{AudioFormat, ChannelPosition},
+///     format::{AudioFormatBuilder, MediaSubtype, MediaType},
+/// };
+///
+/// let pod = AudioFormatBuilder::new(MediaType::Audio, MediaSubtype::Raw)
+///     .format(AudioFormat::F32le)
+///     .rate(48000)
+///     .channel_map(&[ChannelPosition::FrontLeft, ChannelPosition::FrontRight])
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioFormatBuilder {
+    media_type: MediaType,
+    media_subtype: MediaSubtype,
+    format: Option<AudioFormat>,
+    rate: Option<u32>,
+    channels: Option<u32>,
+    channel_map: Option<Vec<ChannelPosition>>,
+}
+
+impl AudioFormatBuilder {
+    /// Start building an audio format with the given media type and subtype.
+    pub fn new(media_type: MediaType, media_subtype: MediaSubtype) -> Self {
+        Self {
+            media_type,
+            media_subtype,
+            format: None,
+            rate: None,
+            channels: None,
+            channel_map: None,
+        }
+    }
+
+    /// Set the sample format.
+    #[must_use]
+    pub fn format(mut self, format: AudioFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Set the sample rate.
+    #[must_use]
+    pub fn rate(mut self, rate: u32) -> Self {
+        self.rate = Some(rate);
+        self
+    }
+
+    /// Set the number of channels.
+    ///
+    /// If not set, the number of channels is the length of the [channel map](Self::channel_map).
+    #[must_use]
+    pub fn channels(mut self, channels: u32) -> Self {
+        self.channels = Some(channels);
+        self
+    }
+
+    /// Set the position of each channel.
+    ///
+    /// # Panics
+    /// If there are more than [`MAX_CHANNELS`](super::audio::MAX_CHANNELS) positions.
+    #[must_use]
+    pub fn channel_map(mut self, positions: &[ChannelPosition]) -> Self {
+        assert!(
+            positions.len() <= MAX_CHANNELS,
+            "Too many channel positions: {}",
+            positions.len()
+        );
+
+        self.channel_map = Some(positions.to_vec());
+        self
+    }
+
+    /// Create the audio info the format is written from.
+    fn to_info(&self) -> AudioInfoRaw {
+        let mut info = AudioInfoRaw::new();
+
+        if let Some(format) = self.format {
+            info.set_format(format);
+        }
+        if let Some(rate) = self.rate {
+            info.set_rate(rate);
+        }
+        match &self.channel_map {
+            Some(channel_map) => info.set_position(channel_map),
+            None => info.set_flags(spa_sys::SPA_AUDIO_FLAG_UNPOSITIONED),
+        }
+        if let Some(channels) = self.channels {
+            info.set_channels(channels);
+        }
+
+        info
+    }
+
+    /// Build the format pod, as a [`ParamType::ENUM_FORMAT`] parameter.
+    ///
+    /// # Errors
+    /// Returns an `ENOSPC` error if the pod could not be written.
+    pub fn build(&self) -> Result<PodOwned, Error> {
+        let mut builder = PodBuilder::new();
+        self.to_info().to_pod_with_media(
+            &mut builder,
+            ParamType::ENUM_FORMAT,
+            self.media_type,
+            self.media_subtype,
+        )?;
+        Ok(builder.build_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw() {
+        assert_eq!(MediaType::Audio.as_raw(), spa_sys::SPA_MEDIA_TYPE_audio);
+        assert_eq!(
+            MediaType::from_raw(spa_sys::SPA_MEDIA_TYPE_video),
+            MediaType::Video
+        );
+        assert_eq!(
+            MediaSubtype::from_raw(spa_sys::SPA_MEDIA_SUBTYPE_mjpg),
+            MediaSubtype::Mjpg
+        );
+        assert_eq!(MediaSubtype::from_raw(u32::MAX), MediaSubtype::Unknown);
+    }
+
+    #[test]
+    fn try_from_raw() {
+        assert_eq!(
+            MediaSubtype::try_from(spa_sys::SPA_MEDIA_SUBTYPE_midi),
+            Ok(MediaSubtype::Midi)
+        );
+        assert_eq!(
+            MediaType::try_from(spa_sys::SPA_MEDIA_TYPE_application),
+            Ok(MediaType::Application)
+        );
+        assert_eq!(MediaType::try_from(u32::MAX), Err(u32::MAX));
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(MediaType::Video.to_string(), "video");
+        assert_eq!(MediaSubtype::Iec958.to_string(), "iec958");
+        assert_eq!("Audio".parse(), Ok(MediaType::Audio));
+        assert_eq!("dsd".parse(), Ok(MediaSubtype::Dsd));
+        assert!("foo".parse::<MediaSubtype>().is_err());
+    }
+
+    #[test]
+    fn filter() {
+        let filter = FormatFilter::new()
+            .require_media_type(MediaType::Audio)
+            .require_rate(44100);
+
+        assert_eq!(
+            filter.to_value(),
+            Value::Object(Object {
+                type_: spa_sys::SPA_TYPE_OBJECT_Format,
+                id: spa_sys::SPA_PARAM_EnumFormat,
+                properties: vec![
+                    Property {
+                        key: spa_sys::SPA_FORMAT_mediaType,
+                        flags: PropertyFlags::empty(),
+                        value: Value::Id(Id(spa_sys::SPA_MEDIA_TYPE_audio)),
+                    },
+                    Property {
+                        key: spa_sys::SPA_FORMAT_AUDIO_rate,
+                        flags: PropertyFlags::empty(),
+                        value: Value::Int(44100),
+                    },
+                ],
+            })
+        );
+
+        let mut builder = PodBuilder::new();
+        filter.build(&mut builder).unwrap();
+        assert!(crate::pod::Pod::validate(builder.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn audio_format_builder() {
+        let pod = AudioFormatBuilder::new(MediaType::Audio, MediaSubtype::Raw)
+            .format(AudioFormat::S16le)
+            .rate(44100)
+            .channel_map(&[ChannelPosition::FrontLeft, ChannelPosition::FrontRight])
+            .build()
+            .unwrap();
+
+        let info = AudioInfoRaw::from_pod(&pod).unwrap();
+        assert_eq!(info.format(), AudioFormat::S16le);
+        assert_eq!(info.rate(), 44100);
+        assert_eq!(info.channels(), 2);
+        assert_eq!(
+            info.position(),
+            vec![ChannelPosition::FrontLeft, ChannelPosition::FrontRight]
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Latency parameters.
+
+use cookie_factory::GenError;
+
+use crate::direction::Direction;
+use crate::pod::{builder::PodBuilder, parser::PodParser, Pod, PropertyFlags};
+use crate::result::Error;
+use crate::utils::Id;
+
+/// The latency of a port in one direction, as found in `SPA_PARAM_Latency` params.
+///
+/// This is the equivalent of a `spa_sys::spa_latency_info`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyInfo {
+    /// The direction the latency applies to.
+    pub direction: Direction,
+    /// The minimum latency, relative to the quantum.
+    pub min_quantum: f32,
+    /// The maximum latency, relative to the quantum.
+    pub max_quantum: f32,
+    /// The minimum latency, relative to the rate.
+    pub min_rate: u32,
+    /// The maximum latency, relative to the rate.
+    pub max_rate: u32,
+    /// The minimum latency, in nanoseconds.
+    pub min_ns: u64,
+    /// The maximum latency, in nanoseconds.
+    pub max_ns: u64,
+}
+
+impl LatencyInfo {
+    /// Create a new `LatencyInfo` in `direction` with no latency.
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            min_quantum: 0.0,
+            max_quantum: 0.0,
+            min_rate: 0,
+            max_rate: 0,
+            min_ns: 0,
+            max_ns: 0,
+        }
+    }
+
+    /// Write the latency info to `builder` as a `SPA_TYPE_OBJECT_ParamLatency` object.
+    pub fn to_pod(&self, builder: &mut PodBuilder) -> Result<(), GenError> {
+        let frame = builder.begin_object(
+            spa_sys::SPA_TYPE_OBJECT_ParamLatency,
+            spa_sys::SPA_PARAM_Latency,
+        );
+
+        builder.push_property(spa_sys::SPA_PARAM_LATENCY_direction, PropertyFlags::empty());
+        builder.push_id(Id(self.direction.as_raw()))?;
+        builder.push_property(
+            spa_sys::SPA_PARAM_LATENCY_minQuantum,
+            PropertyFlags::empty(),
+        );
+        builder.push_float(self.min_quantum)?;
+        builder.push_property(
+            spa_sys::SPA_PARAM_LATENCY_maxQuantum,
+            PropertyFlags::empty(),
+        );
+        builder.push_float(self.max_quantum)?;
+        builder.push_property(spa_sys::SPA_PARAM_LATENCY_minRate, PropertyFlags::empty());
+        builder.push_int(self.min_rate as i32)?;
+        builder.push_property(spa_sys::SPA_PARAM_LATENCY_maxRate, PropertyFlags::empty());
+        builder.push_int(self.max_rate as i32)?;
+        builder.push_property(spa_sys::SPA_PARAM_LATENCY_minNs, PropertyFlags::empty());
+        builder.push_long(self.min_ns as i64)?;
+        builder.push_property(spa_sys::SPA_PARAM_LATENCY_maxNs, PropertyFlags::empty());
+        builder.push_long(self.max_ns as i64)?;
+
+        builder.end_object(frame);
+        Ok(())
+    }
+
+    /// Parse the latency info from a `SPA_TYPE_OBJECT_ParamLatency` object,
+    /// such as a `SPA_PARAM_Latency` param received in a `param` node event.
+    ///
+    /// Like the C `spa_latency_parse()`, only the direction is mandatory,
+    /// missing latencies are set to zero.
+    ///
+    /// # Errors
+    /// Returns an `EINVAL` error if `pod` is not a latency object or is malformed.
+    pub fn from_pod(pod: &Pod) -> Result<Self, Error> {
+        let invalid = |_| Error::new(libc::EINVAL);
+
+        let mut object = PodParser::new(pod).enter_object().map_err(invalid)?;
+        if object.type_() != spa_sys::SPA_TYPE_OBJECT_ParamLatency {
+            return Err(Error::new(libc::EINVAL));
+        }
+
+        let mut direction = None;
+        let mut info = Self::new(Direction::Input);
+
+        while let Some((key, _flags, mut value)) = object.next_property().map_err(invalid)? {
+            match key {
+                spa_sys::SPA_PARAM_LATENCY_direction => {
+                    direction = Some(value.get_id().map_err(invalid)?.0)
+                }
+                spa_sys::SPA_PARAM_LATENCY_minQuantum => {
+                    info.min_quantum = value.get_float().map_err(invalid)?
+                }
+                spa_sys::SPA_PARAM_LATENCY_maxQuantum => {
+                    info.max_quantum = value.get_float().map_err(invalid)?
+                }
+                spa_sys::SPA_PARAM_LATENCY_minRate => {
+                    info.min_rate = value.get_int().map_err(invalid)? as u32
+                }
+                spa_sys::SPA_PARAM_LATENCY_maxRate => {
+                    info.max_rate = value.get_int().map_err(invalid)? as u32
+                }
+                spa_sys::SPA_PARAM_LATENCY_minNs => {
+                    info.min_ns = value.get_long().map_err(invalid)? as u64
+                }
+                spa_sys::SPA_PARAM_LATENCY_maxNs => {
+                    info.max_ns = value.get_long().map_err(invalid)? as u64
+                }
+                _ => {}
+            }
+        }
+
+        info.direction = match direction {
+            Some(spa_sys::SPA_DIRECTION_INPUT) => Direction::Input,
+            Some(spa_sys::SPA_DIRECTION_OUTPUT) => Direction::Output,
+            _ => return Err(Error::new(libc::EINVAL)),
+        };
+
+        Ok(info)
+    }
+}
+
+/// The latency added by a node while processing, as found in `SPA_PARAM_ProcessLatency` params.
+///
+/// This is the equivalent of a `spa_sys::spa_process_latency_info`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProcessLatencyInfo {
+    /// The latency, relative to the quantum.
+    pub quantum: f32,
+    /// The latency, relative to the rate.
+    pub rate: u32,
+    /// The latency, in nanoseconds.
+    pub ns: u64,
+}
+
+impl ProcessLatencyInfo {
+    /// Write the process latency info to `builder` as a `SPA_TYPE_OBJECT_ParamProcessLatency` object.
+    pub fn to_pod(&self, builder: &mut PodBuilder) -> Result<(), GenError> {
+        let frame = builder.begin_object(
+            spa_sys::SPA_TYPE_OBJECT_ParamProcessLatency,
+            spa_sys::SPA_PARAM_ProcessLatency,
+        );
+
+        builder.push_property(
+            spa_sys::SPA_PARAM_PROCESS_LATENCY_quantum,
+            PropertyFlags::empty(),
+        );
+        builder.push_float(self.quantum)?;
+        builder.push_property(
+            spa_sys::SPA_PARAM_PROCESS_LATENCY_rate,
+            PropertyFlags::empty(),
+        );
+        builder.push_int(self.rate as i32)?;
+        builder.push_property(
+            spa_sys::SPA_PARAM_PROCESS_LATENCY_ns,
+            PropertyFlags::empty(),
+        );
+        builder.push_long(self.ns as i64)?;
+
+        builder.end_object(frame);
+        Ok(())
+    }
+
+    /// Parse the process latency info from a `SPA_TYPE_OBJECT_ParamProcessLatency` object.
+    ///
+    /// Missing latencies are set to zero.
+    ///
+    /// # Errors
+    /// Returns an `EINVAL` error if `pod` is not a process latency object or is malformed.
+    pub fn from_pod(pod: &Pod) -> Result<Self, Error> {
+        let invalid = |_| Error::new(libc::EINVAL);
+
+        let mut object = PodParser::new(pod).enter_object().map_err(invalid)?;
+        if object.type_() != spa_sys::SPA_TYPE_OBJECT_ParamProcessLatency {
+            return Err(Error::new(libc::EINVAL));
+        }
+
+        let mut info = Self::default();
+
+        while let Some((key, _flags, mut value)) = object.next_property().map_err(invalid)? {
+            match key {
+                spa_sys::SPA_PARAM_PROCESS_LATENCY_quantum => {
+                    info.quantum = value.get_float().map_err(invalid)?
+                }
+                spa_sys::SPA_PARAM_PROCESS_LATENCY_rate => {
+                    info.rate = value.get_int().map_err(invalid)? as u32
+                }
+                spa_sys::SPA_PARAM_PROCESS_LATENCY_ns => {
+                    info.ns = value.get_long().map_err(invalid)? as u64
+                }
+                _ => {}
+            }
+        }
+
+        Ok(info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_info_pod() {
+        let info = LatencyInfo {
+            min_quantum: 1.0,
+            max_quantum: 2.0,
+            min_rate: 128,
+            max_rate: 256,
+            min_ns: 1_000_000,
+            max_ns: 2_000_000,
+            ..LatencyInfo::new(Direction::Output)
+        };
+
+        let mut builder = PodBuilder::new();
+        info.to_pod(&mut builder).unwrap();
+        assert_eq!(LatencyInfo::from_pod(&builder.build_owned()), Ok(info));
+
+        let mut builder = PodBuilder::new();
+        ProcessLatencyInfo::default().to_pod(&mut builder).unwrap();
+        assert!(LatencyInfo::from_pod(&builder.build_owned()).is_err());
+    }
+
+    #[test]
+    fn process_latency_info_pod() {
+        let info = ProcessLatencyInfo {
+            quantum: 0.5,
+            rate: 64,
+            ns: 500_000,
+        };
+
+        let mut builder = PodBuilder::new();
+        info.to_pod(&mut builder).unwrap();
+        assert_eq!(
+            ProcessLatencyInfo::from_pod(&builder.build_owned()),
+            Ok(info)
+        );
+
+        let mut builder = PodBuilder::new();
+        builder.push_int(1).unwrap();
+        assert!(ProcessLatencyInfo::from_pod(&builder.build_owned()).is_err());
+    }
+}
@@ -0,0 +1,809 @@
+//! Video parameters.
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use super::{
+    format::{MediaSubtype, MediaType},
+    ParamType, ParseNameError,
+};
+use crate::pod::{builder::PodBuilder, parser::PodParser, Pod, PropertyFlags};
+use crate::result::Error;
+use crate::utils::{Fraction, Id, Rectangle};
+
+// Macro generating the VideoFormat enum
+macro_rules! video_formats {
+    ($( ($variant:ident, $raw:ident, $name:literal) ),* $(,)?) => {
+        /// The format of video frames.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        #[allow(missing_docs)]
+        pub enum VideoFormat {
+            $(
+                $variant,
+            )*
+        }
+
+        impl VideoFormat {
+            /// The raw representation of the format
+            pub fn as_raw(&self) -> spa_sys::spa_video_format {
+                match self {
+                    $(
+                        Self::$variant => spa_sys::$raw,
+                    )*
+                }
+            }
+
+            /// Create a `VideoFormat` from a raw `spa_video_format`.
+            ///
+            /// Formats that are not known by this crate are mapped to [`VideoFormat::Unknown`].
+            pub fn from_raw(raw: spa_sys::spa_video_format) -> Self {
+                match raw {
+                    $(
+                        spa_sys::$raw => Self::$variant,
+                    )*
+                    _ => Self::Unknown,
+                }
+            }
+
+            /// The name of the format, as used by PipeWire and GStreamer, such as `NV12`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$variant => $name,
+                    )*
+                }
+            }
+        }
+
+        impl TryFrom<spa_sys::spa_video_format> for VideoFormat {
+            type Error = spa_sys::spa_video_format;
+
+            /// Convert a raw `spa_video_format`, returning it back as error if it is not known by this crate.
+            fn try_from(raw: spa_sys::spa_video_format) -> Result<Self, Self::Error> {
+                match raw {
+                    $(
+                        spa_sys::$raw => Ok(Self::$variant),
+                    )*
+                    _ => Err(raw),
+                }
+            }
+        }
+
+        impl FromStr for VideoFormat {
+            type Err = ParseNameError;
+
+            /// Parse a format from its name, such as `BGRA`, ignoring case.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(
+                    if s.eq_ignore_ascii_case($name) {
+                        return Ok(Self::$variant);
+                    }
+                )*
+
+                Err(ParseNameError::new(s))
+            }
+        }
+
+        impl fmt::Display for VideoFormat {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+    };
+}
+
+video_formats! {
+    // Variant, raw value, name
+    (Unknown, SPA_VIDEO_FORMAT_UNKNOWN, "UNKNOWN"),
+    (Encoded, SPA_VIDEO_FORMAT_ENCODED, "ENCODED"),
+    (I420, SPA_VIDEO_FORMAT_I420, "I420"),
+    (Yv12, SPA_VIDEO_FORMAT_YV12, "YV12"),
+    (Yuy2, SPA_VIDEO_FORMAT_YUY2, "YUY2"),
+    (Uyvy, SPA_VIDEO_FORMAT_UYVY, "UYVY"),
+    (Ayuv, SPA_VIDEO_FORMAT_AYUV, "AYUV"),
+    (Rgbx, SPA_VIDEO_FORMAT_RGBx, "RGBx"),
+    (Bgrx, SPA_VIDEO_FORMAT_BGRx, "BGRx"),
+    (Xrgb, SPA_VIDEO_FORMAT_xRGB, "xRGB"),
+    (Xbgr, SPA_VIDEO_FORMAT_xBGR, "xBGR"),
+    (Rgba, SPA_VIDEO_FORMAT_RGBA, "RGBA"),
+    (Bgra, SPA_VIDEO_FORMAT_BGRA, "BGRA"),
+    (Argb, SPA_VIDEO_FORMAT_ARGB, "ARGB"),
+    (Abgr, SPA_VIDEO_FORMAT_ABGR, "ABGR"),
+    (Rgb, SPA_VIDEO_FORMAT_RGB, "RGB"),
+    (Bgr, SPA_VIDEO_FORMAT_BGR, "BGR"),
+    (Y41b, SPA_VIDEO_FORMAT_Y41B, "Y41B"),
+    (Y42b, SPA_VIDEO_FORMAT_Y42B, "Y42B"),
+    (Yvyu, SPA_VIDEO_FORMAT_YVYU, "YVYU"),
+    (Y444, SPA_VIDEO_FORMAT_Y444, "Y444"),
+    (V210, SPA_VIDEO_FORMAT_v210, "v210"),
+    (V216, SPA_VIDEO_FORMAT_v216, "v216"),
+    (Nv12, SPA_VIDEO_FORMAT_NV12, "NV12"),
+    (Nv21, SPA_VIDEO_FORMAT_NV21, "NV21"),
+    (Gray8, SPA_VIDEO_FORMAT_GRAY8, "GRAY8"),
+    (Gray16be, SPA_VIDEO_FORMAT_GRAY16_BE, "GRAY16_BE"),
+    (Gray16le, SPA_VIDEO_FORMAT_GRAY16_LE, "GRAY16_LE"),
+    (V308, SPA_VIDEO_FORMAT_v308, "v308"),
+    (Rgb16, SPA_VIDEO_FORMAT_RGB16, "RGB16"),
+    (Bgr16, SPA_VIDEO_FORMAT_BGR16, "BGR16"),
+    (Rgb15, SPA_VIDEO_FORMAT_RGB15, "RGB15"),
+    (Bgr15, SPA_VIDEO_FORMAT_BGR15, "BGR15"),
+    (Uyvp, SPA_VIDEO_FORMAT_UYVP, "UYVP"),
+    (A420, SPA_VIDEO_FORMAT_A420, "A420"),
+    (Rgb8p, SPA_VIDEO_FORMAT_RGB8P, "RGB8P"),
+    (Yuv9, SPA_VIDEO_FORMAT_YUV9, "YUV9"),
+    (Yvu9, SPA_VIDEO_FORMAT_YVU9, "YVU9"),
+    (Iyu1, SPA_VIDEO_FORMAT_IYU1, "IYU1"),
+    (Argb64, SPA_VIDEO_FORMAT_ARGB64, "ARGB64"),
+    (Ayuv64, SPA_VIDEO_FORMAT_AYUV64, "AYUV64"),
+    (R210, SPA_VIDEO_FORMAT_r210, "r210"),
+    (I420_10be, SPA_VIDEO_FORMAT_I420_10BE, "I420_10BE"),
+    (I420_10le, SPA_VIDEO_FORMAT_I420_10LE, "I420_10LE"),
+    (I422_10be, SPA_VIDEO_FORMAT_I422_10BE, "I422_10BE"),
+    (I422_10le, SPA_VIDEO_FORMAT_I422_10LE, "I422_10LE"),
+    (Y444_10be, SPA_VIDEO_FORMAT_Y444_10BE, "Y444_10BE"),
+    (Y444_10le, SPA_VIDEO_FORMAT_Y444_10LE, "Y444_10LE"),
+    (Gbr, SPA_VIDEO_FORMAT_GBR, "GBR"),
+    (Gbr10be, SPA_VIDEO_FORMAT_GBR_10BE, "GBR_10BE"),
+    (Gbr10le, SPA_VIDEO_FORMAT_GBR_10LE, "GBR_10LE"),
+    (Nv16, SPA_VIDEO_FORMAT_NV16, "NV16"),
+    (Nv24, SPA_VIDEO_FORMAT_NV24, "NV24"),
+    (Nv12_64z32, SPA_VIDEO_FORMAT_NV12_64Z32, "NV12_64Z32"),
+    (A420_10be, SPA_VIDEO_FORMAT_A420_10BE, "A420_10BE"),
+    (A420_10le, SPA_VIDEO_FORMAT_A420_10LE, "A420_10LE"),
+    (A422_10be, SPA_VIDEO_FORMAT_A422_10BE, "A422_10BE"),
+    (A422_10le, SPA_VIDEO_FORMAT_A422_10LE, "A422_10LE"),
+    (A444_10be, SPA_VIDEO_FORMAT_A444_10BE, "A444_10BE"),
+    (A444_10le, SPA_VIDEO_FORMAT_A444_10LE, "A444_10LE"),
+    (Nv61, SPA_VIDEO_FORMAT_NV61, "NV61"),
+    (P010_10be, SPA_VIDEO_FORMAT_P010_10BE, "P010_10BE"),
+    (P010_10le, SPA_VIDEO_FORMAT_P010_10LE, "P010_10LE"),
+    (Iyu2, SPA_VIDEO_FORMAT_IYU2, "IYU2"),
+    (Vyuy, SPA_VIDEO_FORMAT_VYUY, "VYUY"),
+    (Gbra, SPA_VIDEO_FORMAT_GBRA, "GBRA"),
+    (Gbra10be, SPA_VIDEO_FORMAT_GBRA_10BE, "GBRA_10BE"),
+    (Gbra10le, SPA_VIDEO_FORMAT_GBRA_10LE, "GBRA_10LE"),
+    (Gbr12be, SPA_VIDEO_FORMAT_GBR_12BE, "GBR_12BE"),
+    (Gbr12le, SPA_VIDEO_FORMAT_GBR_12LE, "GBR_12LE"),
+    (Gbra12be, SPA_VIDEO_FORMAT_GBRA_12BE, "GBRA_12BE"),
+    (Gbra12le, SPA_VIDEO_FORMAT_GBRA_12LE, "GBRA_12LE"),
+    (I420_12be, SPA_VIDEO_FORMAT_I420_12BE, "I420_12BE"),
+    (I420_12le, SPA_VIDEO_FORMAT_I420_12LE, "I420_12LE"),
+    (I422_12be, SPA_VIDEO_FORMAT_I422_12BE, "I422_12BE"),
+    (I422_12le, SPA_VIDEO_FORMAT_I422_12LE, "I422_12LE"),
+    (Y444_12be, SPA_VIDEO_FORMAT_Y444_12BE, "Y444_12BE"),
+    (Y444_12le, SPA_VIDEO_FORMAT_Y444_12LE, "Y444_12LE"),
+    (RgbaF16, SPA_VIDEO_FORMAT_RGBA_F16, "RGBA_F16"),
+    (RgbaF32, SPA_VIDEO_FORMAT_RGBA_F32, "RGBA_F32"),
+}
+
+impl VideoFormat {
+    /// Whether the format stores pixels as YUV components.
+    pub fn is_yuv(&self) -> bool {
+        matches!(
+            self,
+            Self::I420
+                | Self::Yv12
+                | Self::Yuy2
+                | Self::Uyvy
+                | Self::Ayuv
+                | Self::Y41b
+                | Self::Y42b
+                | Self::Yvyu
+                | Self::Y444
+                | Self::V210
+                | Self::V216
+                | Self::Nv12
+                | Self::Nv21
+                | Self::V308
+                | Self::Uyvp
+                | Self::A420
+                | Self::Yuv9
+                | Self::Yvu9
+                | Self::Iyu1
+                | Self::Ayuv64
+                | Self::I420_10be
+                | Self::I420_10le
+                | Self::I422_10be
+                | Self::I422_10le
+                | Self::Y444_10be
+                | Self::Y444_10le
+                | Self::Nv16
+                | Self::Nv24
+                | Self::Nv12_64z32
+                | Self::A420_10be
+                | Self::A420_10le
+                | Self::A422_10be
+                | Self::A422_10le
+                | Self::A444_10be
+                | Self::A444_10le
+                | Self::Nv61
+                | Self::P010_10be
+                | Self::P010_10le
+                | Self::Iyu2
+                | Self::Vyuy
+                | Self::I420_12be
+                | Self::I420_12le
+                | Self::I422_12be
+                | Self::I422_12le
+                | Self::Y444_12be
+                | Self::Y444_12le
+        )
+    }
+
+    /// Whether the format stores pixels as RGB components.
+    pub fn is_rgb(&self) -> bool {
+        matches!(
+            self,
+            Self::Rgbx
+                | Self::Bgrx
+                | Self::Xrgb
+                | Self::Xbgr
+                | Self::Rgba
+                | Self::Bgra
+                | Self::Argb
+                | Self::Abgr
+                | Self::Rgb
+                | Self::Bgr
+                | Self::Rgb16
+                | Self::Bgr16
+                | Self::Rgb15
+                | Self::Bgr15
+                | Self::Rgb8p
+                | Self::Argb64
+                | Self::R210
+                | Self::Gbr
+                | Self::Gbr10be
+                | Self::Gbr10le
+                | Self::Gbra
+                | Self::Gbra10be
+                | Self::Gbra10le
+                | Self::Gbr12be
+                | Self::Gbr12le
+                | Self::Gbra12be
+                | Self::Gbra12le
+                | Self::RgbaF16
+                | Self::RgbaF32
+        )
+    }
+
+    /// Whether the format stores its components in more than one plane.
+    pub fn is_planar(&self) -> bool {
+        self.plane_count() > 1
+    }
+
+    /// The number of planes a frame of this format is made of.
+    ///
+    /// Packed and encoded formats use a single plane, while formats with an alpha
+    /// plane, such as [`VideoFormat::A420`], use four.
+    /// Returns 0 for [`VideoFormat::Unknown`].
+    pub fn plane_count(&self) -> usize {
+        match self {
+            Self::Unknown => 0,
+            Self::Nv12
+            | Self::Nv21
+            | Self::Rgb8p
+            | Self::Nv16
+            | Self::Nv24
+            | Self::Nv12_64z32
+            | Self::Nv61
+            | Self::P010_10be
+            | Self::P010_10le => 2,
+            Self::I420
+            | Self::Yv12
+            | Self::Y41b
+            | Self::Y42b
+            | Self::Y444
+            | Self::Yuv9
+            | Self::Yvu9
+            | Self::I420_10be
+            | Self::I420_10le
+            | Self::I422_10be
+            | Self::I422_10le
+            | Self::Y444_10be
+            | Self::Y444_10le
+            | Self::Gbr
+            | Self::Gbr10be
+            | Self::Gbr10le
+            | Self::Gbr12be
+            | Self::Gbr12le
+            | Self::I420_12be
+            | Self::I420_12le
+            | Self::I422_12be
+            | Self::I422_12le
+            | Self::Y444_12be
+            | Self::Y444_12le => 3,
+            Self::A420
+            | Self::A420_10be
+            | Self::A420_10le
+            | Self::A422_10be
+            | Self::A422_10le
+            | Self::A444_10be
+            | Self::A444_10le
+            | Self::Gbra
+            | Self::Gbra10be
+            | Self::Gbra10le
+            | Self::Gbra12be
+            | Self::Gbra12le => 4,
+            _ => 1,
+        }
+    }
+
+    /// The number of bytes used by each pixel of a packed format.
+    ///
+    /// Packed YUV formats with subsampled chroma, such as [`VideoFormat::Yuy2`], use
+    /// the average size of a pixel.
+    /// Returns `None` for planar and encoded formats, and for packed formats whose pixels
+    /// do not use a whole number of bytes, such as [`VideoFormat::V210`].
+    pub fn bytes_per_pixel(&self) -> Option<u32> {
+        match self {
+            Self::Gray8 => Some(1),
+            Self::Yuy2
+            | Self::Uyvy
+            | Self::Yvyu
+            | Self::Vyuy
+            | Self::Gray16be
+            | Self::Gray16le
+            | Self::Rgb16
+            | Self::Bgr16
+            | Self::Rgb15
+            | Self::Bgr15 => Some(2),
+            Self::Rgb | Self::Bgr | Self::V308 | Self::Iyu2 => Some(3),
+            Self::Ayuv
+            | Self::Rgbx
+            | Self::Bgrx
+            | Self::Xrgb
+            | Self::Xbgr
+            | Self::Rgba
+            | Self::Bgra
+            | Self::Argb
+            | Self::Abgr
+            | Self::V216
+            | Self::R210 => Some(4),
+            Self::Argb64 | Self::Ayuv64 | Self::RgbaF16 => Some(8),
+            Self::RgbaF32 => Some(16),
+            _ => None,
+        }
+    }
+
+    /// Whether the frames are encoded, such as with a compressed format.
+    ///
+    /// The actual encoding is described by the media subtype of the format.
+    pub fn is_encoded(&self) -> bool {
+        matches!(self, Self::Encoded)
+    }
+}
+
+// Macro generating the enums describing the properties of a raw video format
+macro_rules! video_enums {
+    ($(
+        $(#[$meta:meta])*
+        $enum:ident: $raw_type:ident {
+            $( ($variant:ident, $raw:ident) ),* $(,)?
+        }
+    )*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+            #[allow(missing_docs)]
+            pub enum $enum {
+                $(
+                    $variant,
+                )*
+                /// A value that is not known by this crate.
+                Other(u32),
+            }
+
+            impl $enum {
+                /// The raw representation of the value
+                pub fn as_raw(&self) -> spa_sys::$raw_type {
+                    match self {
+                        $(
+                            Self::$variant => spa_sys::$raw,
+                        )*
+                        Self::Other(raw) => *raw,
+                    }
+                }
+
+                /// Create the value from its raw representation, unknown values being kept as `Other`.
+                pub fn from_raw(raw: spa_sys::$raw_type) -> Self {
+                    match raw {
+                        $(
+                            spa_sys::$raw => Self::$variant,
+                        )*
+                        raw => Self::Other(raw),
+                    }
+                }
+            }
+
+            impl Default for $enum {
+                fn default() -> Self {
+                    Self::from_raw(0)
+                }
+            }
+        )*
+    };
+}
+
+video_enums! {
+    /// How the fields of the video frames are interlaced.
+    VideoInterlaceMode: spa_video_interlace_mode {
+        (Progressive, SPA_VIDEO_INTERLACE_MODE_PROGRESSIVE),
+        (Interleaved, SPA_VIDEO_INTERLACE_MODE_INTERLEAVED),
+        (Mixed, SPA_VIDEO_INTERLACE_MODE_MIXED),
+        (Fields, SPA_VIDEO_INTERLACE_MODE_FIELDS),
+    }
+
+    /// The range of the color values.
+    VideoColorRange: spa_video_color_range {
+        (Unknown, SPA_VIDEO_COLOR_RANGE_UNKNOWN),
+        (Full, SPA_VIDEO_COLOR_RANGE_0_255),
+        (Limited, SPA_VIDEO_COLOR_RANGE_16_235),
+    }
+
+    /// The matrix used to convert between YUV and RGB.
+    VideoColorMatrix: spa_video_color_matrix {
+        (Unknown, SPA_VIDEO_COLOR_MATRIX_UNKNOWN),
+        (Rgb, SPA_VIDEO_COLOR_MATRIX_RGB),
+        (Fcc, SPA_VIDEO_COLOR_MATRIX_FCC),
+        (Bt709, SPA_VIDEO_COLOR_MATRIX_BT709),
+        (Bt601, SPA_VIDEO_COLOR_MATRIX_BT601),
+        (Smpte240m, SPA_VIDEO_COLOR_MATRIX_SMPTE240M),
+        (Bt2020, SPA_VIDEO_COLOR_MATRIX_BT2020),
+    }
+
+    /// The transfer function used to encode the color values.
+    VideoTransferFunction: spa_video_transfer_function {
+        (Unknown, SPA_VIDEO_TRANSFER_UNKNOWN),
+        (Gamma10, SPA_VIDEO_TRANSFER_GAMMA10),
+        (Gamma18, SPA_VIDEO_TRANSFER_GAMMA18),
+        (Gamma20, SPA_VIDEO_TRANSFER_GAMMA20),
+        (Gamma22, SPA_VIDEO_TRANSFER_GAMMA22),
+        (Bt709, SPA_VIDEO_TRANSFER_BT709),
+        (Smpte240m, SPA_VIDEO_TRANSFER_SMPTE240M),
+        (Srgb, SPA_VIDEO_TRANSFER_SRGB),
+        (Gamma28, SPA_VIDEO_TRANSFER_GAMMA28),
+        (Log100, SPA_VIDEO_TRANSFER_LOG100),
+        (Log316, SPA_VIDEO_TRANSFER_LOG316),
+        (Bt2020_12, SPA_VIDEO_TRANSFER_BT2020_12),
+        (AdobeRgb, SPA_VIDEO_TRANSFER_ADOBERGB),
+    }
+
+    /// The color primaries, defining the mapping of color values to actual colors.
+    VideoColorPrimaries: spa_video_color_primaries {
+        (Unknown, SPA_VIDEO_COLOR_PRIMARIES_UNKNOWN),
+        (Bt709, SPA_VIDEO_COLOR_PRIMARIES_BT709),
+        (Bt470m, SPA_VIDEO_COLOR_PRIMARIES_BT470M),
+        (Bt470bg, SPA_VIDEO_COLOR_PRIMARIES_BT470BG),
+        (Smpte170m, SPA_VIDEO_COLOR_PRIMARIES_SMPTE170M),
+        (Smpte240m, SPA_VIDEO_COLOR_PRIMARIES_SMPTE240M),
+        (Film, SPA_VIDEO_COLOR_PRIMARIES_FILM),
+        (Bt2020, SPA_VIDEO_COLOR_PRIMARIES_BT2020),
+        (AdobeRgb, SPA_VIDEO_COLOR_PRIMARIES_ADOBERGB),
+    }
+}
+
+/// How the color values of video frames map to actual colors.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct VideoColorimetry {
+    /// The range of the color values.
+    pub range: VideoColorRange,
+    /// The matrix used to convert between YUV and RGB.
+    pub matrix: VideoColorMatrix,
+    /// The transfer function used to encode the color values.
+    pub transfer: VideoTransferFunction,
+    /// The color primaries.
+    pub primaries: VideoColorPrimaries,
+}
+
+/// The description of a raw video format.
+///
+/// Unlike [`AudioInfoRaw`](super::audio::AudioInfoRaw), this does not wrap the C `spa_video_info_raw`
+/// as its layout changed across PipeWire versions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VideoInfoRaw {
+    /// The format of the frames.
+    pub format: VideoFormat,
+    /// The raw `SPA_VIDEO_FLAG_*` flags.
+    ///
+    /// Those are not part of format pods, so they are neither written by [`to_pod`](Self::to_pod)
+    /// nor read by [`from_pod`](Self::from_pod).
+    pub flags: u32,
+    /// The size of the frames, in pixels.
+    pub size: Rectangle,
+    /// The frame rate, `0/1` for variable frame rates.
+    pub framerate: Fraction,
+    /// The maximum frame rate, for variable frame rates.
+    pub max_framerate: Fraction,
+    /// The number of views, for multiview video.
+    pub views: u32,
+    /// How the fields of the frames are interlaced.
+    pub interlace_mode: VideoInterlaceMode,
+    /// The pixel aspect ratio.
+    pub pixel_aspect_ratio: Fraction,
+    /// How the color values map to actual colors.
+    pub colorimetry: VideoColorimetry,
+}
+
+impl VideoInfoRaw {
+    /// Create a new `VideoInfoRaw` with an unknown format and no size.
+    pub fn new() -> Self {
+        Self {
+            format: VideoFormat::Unknown,
+            flags: 0,
+            size: Rectangle {
+                width: 0,
+                height: 0,
+            },
+            framerate: Fraction { num: 0, denom: 0 },
+            max_framerate: Fraction { num: 0, denom: 0 },
+            views: 0,
+            interlace_mode: VideoInterlaceMode::Progressive,
+            pixel_aspect_ratio: Fraction { num: 0, denom: 0 },
+            colorimetry: VideoColorimetry::default(),
+        }
+    }
+
+    /// Write the video info to `builder` as a raw video `SPA_TYPE_OBJECT_Format` object,
+    /// whose id is the parameter `id`, usually [`ParamType::ENUM_FORMAT`] or [`ParamType::FORMAT`].
+    ///
+    /// Like the C `spa_format_video_raw_build()`, the properties are only written if they are set.
+    ///
+    /// # Errors
+    /// Returns an `ENOSPC` error if the pod could not be written to the builder.
+    pub fn to_pod(&self, builder: &mut PodBuilder, id: ParamType) -> Result<(), Error> {
+        let frame = builder.begin_object(spa_sys::SPA_TYPE_OBJECT_Format, id.as_raw());
+
+        builder.push_property(spa_sys::SPA_FORMAT_mediaType, PropertyFlags::empty());
+        builder.push_id(Id(MediaType::Video.as_raw()))?;
+        builder.push_property(spa_sys::SPA_FORMAT_mediaSubtype, PropertyFlags::empty());
+        builder.push_id(Id(MediaSubtype::Raw.as_raw()))?;
+
+        if self.format != VideoFormat::Unknown {
+            builder.push_property(spa_sys::SPA_FORMAT_VIDEO_format, PropertyFlags::empty());
+            builder.push_id(Id(self.format.as_raw()))?;
+        }
+        if self.size.width != 0 && self.size.height != 0 {
+            builder.push_property(spa_sys::SPA_FORMAT_VIDEO_size, PropertyFlags::empty());
+            builder.push_rectangle(self.size)?;
+        }
+        if self.framerate.denom != 0 {
+            builder.push_property(spa_sys::SPA_FORMAT_VIDEO_framerate, PropertyFlags::empty());
+            builder.push_fraction(self.framerate)?;
+        }
+        if self.max_framerate.denom != 0 {
+            builder.push_property(
+                spa_sys::SPA_FORMAT_VIDEO_maxFramerate,
+                PropertyFlags::empty(),
+            );
+            builder.push_fraction(self.max_framerate)?;
+        }
+        if self.views != 0 {
+            builder.push_property(spa_sys::SPA_FORMAT_VIDEO_views, PropertyFlags::empty());
+            builder.push_int(self.views as i32)?;
+        }
+        if self.interlace_mode != VideoInterlaceMode::Progressive {
+            builder.push_property(
+                spa_sys::SPA_FORMAT_VIDEO_interlaceMode,
+                PropertyFlags::empty(),
+            );
+            builder.push_id(Id(self.interlace_mode.as_raw()))?;
+        }
+        if self.pixel_aspect_ratio.denom != 0 {
+            builder.push_property(
+                spa_sys::SPA_FORMAT_VIDEO_pixelAspectRatio,
+                PropertyFlags::empty(),
+            );
+            builder.push_fraction(self.pixel_aspect_ratio)?;
+        }
+
+        let colorimetry = [
+            (
+                spa_sys::SPA_FORMAT_VIDEO_colorRange,
+                self.colorimetry.range.as_raw(),
+            ),
+            (
+                spa_sys::SPA_FORMAT_VIDEO_colorMatrix,
+                self.colorimetry.matrix.as_raw(),
+            ),
+            (
+                spa_sys::SPA_FORMAT_VIDEO_transferFunction,
+                self.colorimetry.transfer.as_raw(),
+            ),
+            (
+                spa_sys::SPA_FORMAT_VIDEO_colorPrimaries,
+                self.colorimetry.primaries.as_raw(),
+            ),
+        ];
+        for (key, raw) in colorimetry.iter() {
+            if *raw != 0 {
+                builder.push_property(*key, PropertyFlags::empty());
+                builder.push_id(Id(*raw))?;
+            }
+        }
+
+        builder.end_object(frame);
+        Ok(())
+    }
+
+    /// Parse the video info from a raw video `SPA_TYPE_OBJECT_Format` object,
+    /// such as the format received in a `param_changed` stream callback.
+    ///
+    /// The format must be fixated: properties whose value is a choice are rejected.
+    ///
+    /// # Errors
+    /// Returns an `EINVAL` error if `pod` is not a raw video format object or is malformed.
+    pub fn from_pod(pod: &Pod) -> Result<Self, Error> {
+        let invalid = |_| Error::new(libc::EINVAL);
+
+        let mut object = PodParser::new(pod).enter_object().map_err(invalid)?;
+        if object.type_() != spa_sys::SPA_TYPE_OBJECT_Format {
+            return Err(Error::new(libc::EINVAL));
+        }
+
+        let mut info = Self::new();
+        let mut media_type = None;
+        let mut media_subtype = None;
+
+        while let Some((key, _flags, mut value)) = object.next_property().map_err(invalid)? {
+            match key {
+                spa_sys::SPA_FORMAT_mediaType => {
+                    media_type = Some(MediaType::from_raw(value.get_id().map_err(invalid)?.0))
+                }
+                spa_sys::SPA_FORMAT_mediaSubtype => {
+                    media_subtype = Some(MediaSubtype::from_raw(value.get_id().map_err(invalid)?.0))
+                }
+                spa_sys::SPA_FORMAT_VIDEO_format => {
+                    info.format = VideoFormat::from_raw(value.get_id().map_err(invalid)?.0)
+                }
+                spa_sys::SPA_FORMAT_VIDEO_size => {
+                    info.size = value.get_rectangle().map_err(invalid)?
+                }
+                spa_sys::SPA_FORMAT_VIDEO_framerate => {
+                    info.framerate = value.get_fraction().map_err(invalid)?
+                }
+                spa_sys::SPA_FORMAT_VIDEO_maxFramerate => {
+                    info.max_framerate = value.get_fraction().map_err(invalid)?
+                }
+                spa_sys::SPA_FORMAT_VIDEO_views => {
+                    info.views = value.get_int().map_err(invalid)? as u32
+                }
+                spa_sys::SPA_FORMAT_VIDEO_interlaceMode => {
+                    info.interlace_mode =
+                        VideoInterlaceMode::from_raw(value.get_id().map_err(invalid)?.0)
+                }
+                spa_sys::SPA_FORMAT_VIDEO_pixelAspectRatio => {
+                    info.pixel_aspect_ratio = value.get_fraction().map_err(invalid)?
+                }
+                spa_sys::SPA_FORMAT_VIDEO_colorRange => {
+                    info.colorimetry.range =
+                        VideoColorRange::from_raw(value.get_id().map_err(invalid)?.0)
+                }
+                spa_sys::SPA_FORMAT_VIDEO_colorMatrix => {
+                    info.colorimetry.matrix =
+                        VideoColorMatrix::from_raw(value.get_id().map_err(invalid)?.0)
+                }
+                spa_sys::SPA_FORMAT_VIDEO_transferFunction => {
+                    info.colorimetry.transfer =
+                        VideoTransferFunction::from_raw(value.get_id().map_err(invalid)?.0)
+                }
+                spa_sys::SPA_FORMAT_VIDEO_colorPrimaries => {
+                    info.colorimetry.primaries =
+                        VideoColorPrimaries::from_raw(value.get_id().map_err(invalid)?.0)
+                }
+                _ => {}
+            }
+        }
+
+        if media_type != Some(MediaType::Video) || media_subtype != Some(MediaSubtype::Raw) {
+            return Err(Error::new(libc::EINVAL));
+        }
+
+        Ok(info)
+    }
+}
+
+impl Default for VideoInfoRaw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw() {
+        assert_eq!(VideoFormat::Rgbx.as_raw(), spa_sys::SPA_VIDEO_FORMAT_RGBx);
+        assert_eq!(
+            VideoFormat::from_raw(spa_sys::SPA_VIDEO_FORMAT_NV12),
+            VideoFormat::Nv12
+        );
+        assert_eq!(
+            VideoFormat::try_from(spa_sys::SPA_VIDEO_FORMAT_YUY2),
+            Ok(VideoFormat::Yuy2)
+        );
+        assert_eq!(VideoFormat::try_from(u32::MAX), Err(u32::MAX));
+    }
+
+    #[test]
+    fn predicates() {
+        assert!(VideoFormat::I420.is_yuv());
+        assert!(VideoFormat::I420.is_planar());
+        assert!(!VideoFormat::I420.is_rgb());
+
+        assert!(VideoFormat::Yuy2.is_yuv());
+        assert!(!VideoFormat::Yuy2.is_planar());
+
+        assert!(VideoFormat::Bgra.is_rgb());
+        assert!(!VideoFormat::Bgra.is_yuv());
+        assert!(!VideoFormat::Bgra.is_encoded());
+
+        assert!(!VideoFormat::Gray8.is_yuv());
+        assert!(!VideoFormat::Gray8.is_rgb());
+
+        assert!(VideoFormat::Encoded.is_encoded());
+    }
+
+    #[test]
+    fn plane_count() {
+        assert_eq!(VideoFormat::Unknown.plane_count(), 0);
+        assert_eq!(VideoFormat::Encoded.plane_count(), 1);
+        assert_eq!(VideoFormat::Yuy2.plane_count(), 1);
+        assert_eq!(VideoFormat::Ayuv.plane_count(), 1);
+        assert_eq!(VideoFormat::Nv12.plane_count(), 2);
+        assert_eq!(VideoFormat::Nv21.plane_count(), 2);
+        assert_eq!(VideoFormat::I420.plane_count(), 3);
+        assert_eq!(VideoFormat::Y444.plane_count(), 3);
+        assert_eq!(VideoFormat::A420.plane_count(), 4);
+        assert_eq!(VideoFormat::Gbra.plane_count(), 4);
+    }
+
+    #[test]
+    fn bytes_per_pixel() {
+        assert_eq!(VideoFormat::Gray8.bytes_per_pixel(), Some(1));
+        assert_eq!(VideoFormat::Yuy2.bytes_per_pixel(), Some(2));
+        assert_eq!(VideoFormat::Rgb.bytes_per_pixel(), Some(3));
+        assert_eq!(VideoFormat::Bgra.bytes_per_pixel(), Some(4));
+        assert_eq!(VideoFormat::RgbaF32.bytes_per_pixel(), Some(16));
+        assert_eq!(VideoFormat::Nv12.bytes_per_pixel(), None);
+        assert_eq!(VideoFormat::V210.bytes_per_pixel(), None);
+        assert_eq!(VideoFormat::Encoded.bytes_per_pixel(), None);
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(VideoFormat::Bgra.to_string(), "BGRA");
+        assert_eq!(VideoFormat::Xrgb.to_string(), "xRGB");
+        assert_eq!("NV12".parse(), Ok(VideoFormat::Nv12));
+        assert_eq!("I420_10LE".parse(), Ok(VideoFormat::I420_10le));
+        assert_eq!("nv12".parse(), Ok(VideoFormat::Nv12));
+        assert!("NV".parse::<VideoFormat>().is_err());
+    }
+
+    #[test]
+    fn video_info_raw_pod() {
+        let mut info = VideoInfoRaw::new();
+        info.format = VideoFormat::Rgbx;
+        info.size = Rectangle {
+            width: 1920,
+            height: 1080,
+        };
+        info.framerate = Fraction { num: 30, denom: 1 };
+        info.colorimetry.range = VideoColorRange::Full;
+
+        let mut builder = PodBuilder::new();
+        info.to_pod(&mut builder, ParamType::FORMAT).unwrap();
+        let pod = builder.build_owned();
+
+        let parsed = VideoInfoRaw::from_pod(&pod).unwrap();
+        assert_eq!(parsed, info);
+        assert_eq!(parsed.interlace_mode, VideoInterlaceMode::Progressive);
+        assert_eq!(parsed.colorimetry.matrix, VideoColorMatrix::Unknown);
+
+        let mut audio = crate::param::audio::AudioInfoRaw::new();
+        audio.set_rate(48000);
+        let mut builder = PodBuilder::new();
+        audio.to_pod(&mut builder, ParamType::ENUM_FORMAT).unwrap();
+        assert!(VideoInfoRaw::from_pod(&builder.build_owned()).is_err());
+    }
+}
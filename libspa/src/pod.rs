@@ -3,16 +3,46 @@
 //!
 //! Everything concerning serializing raw pods from rust types is in the [`serialize`] submodule.
 //! and everything about deserializing rust types from raw pods is in the [`deserialize`] submodule.
+//! Pods can also be built incrementally using the [`PodBuilder`](`builder::PodBuilder`) from the [`builder`] submodule,
+//! and read one at a time using the [`PodParser`](`parser::PodParser`) from the [`parser`] submodule.
+//! With the `serde` feature, the `serde` submodule allows converting any type implementing the `serde` traits
+//! from and to pods.
 //!
 //! The entire serialization and deserialization approach is inspired by and similar to the excellent `serde` crate,
 //! but is much more specialized to fit the SPA pod format.
 
+pub mod builder;
 pub mod deserialize;
+pub mod parser;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod serialize;
 
+/// Builder for `Struct` pods, also available from the [`builder`] module.
+pub mod struct_ {
+    pub use super::builder::PodStructBuilder;
+}
+
+/// Builder for `Object` pods, also available from the [`builder`] module.
+pub mod object_ {
+    pub use super::builder::PodObjectBuilder;
+}
+
+/// Builder for `Choice` pods, also available from the [`builder`] module.
+pub mod choice_ {
+    pub use super::builder::PodChoiceBuilder;
+}
+
+/// Builder for `Sequence` pods, also available from the [`builder`] module.
+pub mod sequence_ {
+    pub use super::builder::PodSequenceBuilder;
+}
+
 use std::{
+    convert::TryFrom,
     ffi::c_void,
     io::{Seek, Write},
+    mem,
 };
 
 use bitflags::bitflags;
@@ -34,7 +64,7 @@ use nom::{
 use deserialize::{BoolVisitor, NoneVisitor, PodDeserialize, PodDeserializer};
 use serialize::{PodSerialize, PodSerializer};
 
-use crate::utils::{Choice, Fd, Fraction, Id, Rectangle};
+use crate::utils::{Choice, Fd, Fraction, Id, Rectangle, SpaType};
 
 use self::deserialize::{
     ChoiceDoubleVisitor, ChoiceFdVisitor, ChoiceFloatVisitor, ChoiceFractionVisitor,
@@ -43,6 +73,315 @@ use self::deserialize::{
     RectangleVisitor,
 };
 
+/// A transparent wrapper around a `spa_sys::spa_pod`.
+///
+/// Only the pod header is part of this struct, the body of the pod follows it in memory.
+/// This means that a `Pod` should only be accessed through a reference, pointing to a full pod.
+#[repr(transparent)]
+pub struct Pod(spa_sys::spa_pod);
+
+impl Pod {
+    /// Create a `&Pod` from a raw `spa_pod` pointer.
+    ///
+    /// # Safety
+    /// - The provided pointer must point to a valid, well-aligned `spa_pod`, including its body.
+    /// - The pod must not be mutated and must stay valid for the lifetime `'a`.
+    pub unsafe fn from_raw<'a>(pod: *const spa_sys::spa_pod) -> &'a Self {
+        &*(pod as *const Pod)
+    }
+
+    /// Obtain a pointer to the underlying `spa_pod`.
+    pub fn as_raw_ptr(&self) -> *mut spa_sys::spa_pod {
+        &self.0 as *const _ as *mut _
+    }
+
+    /// The type of the pod.
+    ///
+    /// Returns the raw type as error if it is not a known [`SpaType`].
+    pub fn type_(&self) -> Result<SpaType, u32> {
+        SpaType::try_from(self.0.type_)
+    }
+
+    /// The size of the pod body, not including the header.
+    pub fn size(&self) -> u32 {
+        self.0.size
+    }
+
+    /// The size of the pod including its header, rounded up to the 8 bytes alignment of pods.
+    ///
+    /// This is the `SPA_POD_SIZE()` of the pod padded to 8 bytes,
+    /// which is the offset of the next pod when pods are laid out one after the other.
+    pub fn size_including_header(&self) -> usize {
+        let size = mem::size_of::<spa_sys::spa_pod>() + self.0.size as usize;
+        (size + 7) & !7
+    }
+
+    /// The raw bytes of the pod, including its header.
+    pub fn as_bytes(&self) -> &[u8] {
+        let size = mem::size_of::<spa_sys::spa_pod>() + self.0.size as usize;
+        unsafe { std::slice::from_raw_parts(self.as_raw_ptr() as *const u8, size) }
+    }
+
+    /// Create a `&Pod` from a byte slice containing a raw pod.
+    ///
+    /// Returns `None` if `bytes` is too small to contain a pod header.
+    ///
+    /// # Safety
+    /// - `bytes` must be suitably aligned for a `spa_pod`.
+    /// - `bytes` must contain a full, valid pod, including its body.
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> Option<&Self> {
+        if bytes.len() < mem::size_of::<spa_sys::spa_pod>() {
+            return None;
+        }
+
+        Some(Self::from_raw(bytes.as_ptr().cast()))
+    }
+
+    /// Create a `&Pod` from a byte slice containing a raw pod.
+    ///
+    /// This checks that the slice is aligned for a `spa_pod` and large enough to
+    /// contain the pod body declared in its header, but does not check the body itself.
+    ///
+    /// # Errors
+    /// Returns an `EINVAL` error if the checks fail.
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, crate::result::Error> {
+        let header_size = mem::size_of::<spa_sys::spa_pod>();
+
+        if bytes.len() < header_size
+            || bytes.as_ptr() as usize % mem::align_of::<spa_sys::spa_pod>() != 0
+        {
+            return Err(crate::result::Error::new(libc::EINVAL));
+        }
+
+        let pod = unsafe { Self::from_raw(bytes.as_ptr().cast()) };
+        match header_size.checked_add(pod.0.size as usize) {
+            Some(size) if size <= bytes.len() => {}
+            _ => return Err(crate::result::Error::new(libc::EINVAL)),
+        }
+
+        Ok(pod)
+    }
+
+    /// Check the structural integrity of the raw pod contained in `bytes`.
+    ///
+    /// The pod size must fit in `bytes`, the bodies of compound pods (structs, objects,
+    /// sequences, arrays and choices) must be consistent with the pods they contain
+    /// and strings must be null-terminated.
+    /// Pods nested more than [`MAX_DEPTH`] levels deep are rejected.
+    /// Use this before parsing a pod coming from an untrusted source.
+    ///
+    /// # Errors
+    /// Returns an `EINVAL` error if the pod is malformed.
+    pub fn validate(bytes: &[u8]) -> Result<(), crate::result::Error> {
+        validate_pod(bytes, 0)
+            .map(|_| ())
+            .ok_or_else(|| crate::result::Error::new(libc::EINVAL))
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for &'a Pod {
+    type Error = crate::result::Error;
+
+    /// See [`Pod::from_bytes`].
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        Pod::from_bytes(bytes)
+    }
+}
+
+/// An owned pod, stored in a buffer suitably aligned for a `spa_pod`.
+///
+/// It dereferences to [`Pod`], so it can be used wherever a `&Pod` is expected.
+/// A `PodOwned` can be created by copying a borrowed pod with [`ToOwned::to_owned`],
+/// or from raw bytes with [`from_bytes`](Self::from_bytes).
+#[derive(Clone)]
+pub struct PodOwned {
+    // Stored as u64 to keep the pod 8-byte aligned.
+    data: Vec<u64>,
+    len: usize,
+}
+
+impl PodOwned {
+    /// Create an owned pod by copying the pod contained in `bytes`.
+    ///
+    /// `bytes` does not need to be aligned, but the same checks as [`Pod::from_bytes`]
+    /// are performed on it otherwise.
+    /// Any trailing bytes after the pod are ignored.
+    ///
+    /// # Errors
+    /// Returns an `EINVAL` error if the checks fail.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::result::Error> {
+        let size = read_u32(bytes, 0)
+            .and_then(|size| (size as usize).checked_add(mem::size_of::<spa_sys::spa_pod>()))
+            .filter(|size| *size <= bytes.len())
+            .ok_or_else(|| crate::result::Error::new(libc::EINVAL))?;
+
+        let mut data = vec![0u64; (size + 7) / 8];
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), data.as_mut_ptr().cast(), size);
+        }
+
+        Ok(Self { data, len: size })
+    }
+
+    /// Get the raw bytes of the pod, including its header.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr().cast(), self.len) }
+    }
+
+    /// Get the pod as a borrowed [`Pod`].
+    pub fn as_pod(&self) -> &Pod {
+        unsafe { Pod::from_raw(self.data.as_ptr().cast()) }
+    }
+
+    /// Consume the pod, returning its raw bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+impl std::ops::Deref for PodOwned {
+    type Target = Pod;
+
+    fn deref(&self) -> &Pod {
+        self.as_pod()
+    }
+}
+
+impl AsRef<Pod> for PodOwned {
+    fn as_ref(&self) -> &Pod {
+        self.as_pod()
+    }
+}
+
+impl std::borrow::Borrow<Pod> for PodOwned {
+    fn borrow(&self) -> &Pod {
+        self.as_pod()
+    }
+}
+
+impl ToOwned for Pod {
+    type Owned = PodOwned;
+
+    fn to_owned(&self) -> PodOwned {
+        PodOwned::from_bytes(self.as_bytes()).expect("pod size is inconsistent")
+    }
+}
+
+impl From<&Pod> for PodOwned {
+    fn from(pod: &Pod) -> Self {
+        pod.to_owned()
+    }
+}
+
+impl std::fmt::Debug for PodOwned {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PodOwned")
+            .field("size", &self.size())
+            .field("type", &self.as_pod().0.type_)
+            .finish()
+    }
+}
+
+/// Read the native endian `u32` at `offset` in `bytes`, if it is in bounds.
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let end = offset.checked_add(4)?;
+    let b = bytes.get(offset..end)?;
+    Some(u32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// The maximum nesting depth of the pods accepted by [`Pod::validate`].
+pub const MAX_DEPTH: usize = 64;
+
+/// Validate the pod at the start of `bytes`, nested `depth` levels deep,
+/// returning its size including the header.
+fn validate_pod(bytes: &[u8], depth: usize) -> Option<usize> {
+    let size = read_u32(bytes, 0)? as usize;
+    let type_ = read_u32(bytes, 4)?;
+    let body = bytes.get(8..8usize.checked_add(size)?)?;
+
+    validate_body(type_, body, depth)?;
+
+    Some(8 + size)
+}
+
+/// Validate the body of a pod of type `type_`, nested `depth` levels deep.
+fn validate_body(type_: u32, body: &[u8], depth: usize) -> Option<()> {
+    // Bound the recursion, so that deeply nested pods cannot overflow the stack.
+    if depth > MAX_DEPTH {
+        return None;
+    }
+
+    let min_size = match type_ {
+        spa_sys::SPA_TYPE_Bool | spa_sys::SPA_TYPE_Id | spa_sys::SPA_TYPE_Int => 4,
+        spa_sys::SPA_TYPE_Float => 4,
+        spa_sys::SPA_TYPE_Long | spa_sys::SPA_TYPE_Double | spa_sys::SPA_TYPE_Fd => 8,
+        spa_sys::SPA_TYPE_Rectangle | spa_sys::SPA_TYPE_Fraction => 8,
+        spa_sys::SPA_TYPE_Pointer => mem::size_of::<spa_sys::spa_pod_pointer_body>(),
+        spa_sys::SPA_TYPE_String => 1,
+        // Child pod header
+        spa_sys::SPA_TYPE_Array => 8,
+        // Object type and id, sequence unit and padding
+        spa_sys::SPA_TYPE_Object | spa_sys::SPA_TYPE_Sequence => 8,
+        // Choice type and flags, followed by the child pod header
+        spa_sys::SPA_TYPE_Choice => 16,
+        _ => 0,
+    };
+    if body.len() < min_size {
+        return None;
+    }
+
+    match type_ {
+        spa_sys::SPA_TYPE_String => {
+            if body.last() != Some(&0) {
+                return None;
+            }
+        }
+        spa_sys::SPA_TYPE_Array => validate_array_body(body, depth)?,
+        spa_sys::SPA_TYPE_Choice => validate_array_body(&body[8..], depth)?,
+        spa_sys::SPA_TYPE_Struct => validate_children(body, 0, depth)?,
+        // Each property has a key and flags before its value
+        spa_sys::SPA_TYPE_Object => validate_children(&body[8..], 8, depth)?,
+        // Each control has an offset and type before its value
+        spa_sys::SPA_TYPE_Sequence => validate_children(&body[8..], 8, depth)?,
+        spa_sys::SPA_TYPE_Pod => {
+            validate_pod(body, depth + 1)?;
+        }
+        _ => {}
+    }
+
+    Some(())
+}
+
+/// Validate the children of a compound pod nested `depth` levels deep, each being preceded by `prefix` bytes.
+fn validate_children(mut bytes: &[u8], prefix: usize, depth: usize) -> Option<()> {
+    while !bytes.is_empty() {
+        let size = prefix + validate_pod(bytes.get(prefix..)?, depth + 1)?;
+        // The last child may not be padded.
+        let padded = (size + 7) & !7;
+        bytes = bytes.get(padded..).unwrap_or(&[]);
+    }
+
+    Some(())
+}
+
+/// Validate the body of an array pod nested `depth` levels deep, a child pod header followed by its elements.
+fn validate_array_body(body: &[u8], depth: usize) -> Option<()> {
+    let child_size = read_u32(body, 0)? as usize;
+    let child_type = read_u32(body, 4)?;
+    let elements = &body[8..];
+
+    if child_size == 0 {
+        return if elements.is_empty() { Some(()) } else { None };
+    }
+    if elements.len() % child_size != 0 {
+        return None;
+    }
+
+    elements
+        .chunks(child_size)
+        .try_for_each(|element| validate_body(child_type, element, depth + 1))
+}
+
 /// Implementors of this trait are the canonical representation of a specific type of fixed sized SPA pod.
 ///
 /// They can be used as an output type for [`FixedSizedPod`] implementors
@@ -727,3 +1066,104 @@ bitflags! {
         const HINT_DICT = spa_sys::SPA_POD_PROP_FLAG_HINT_DICT;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn int_pod_bytes() -> Vec<u8> {
+        PodSerializer::serialize(Cursor::new(Vec::new()), &Value::Int(42))
+            .unwrap()
+            .0
+            .into_inner()
+    }
+
+    #[test]
+    fn from_bytes() {
+        let bytes = int_pod_bytes();
+
+        let pod = Pod::from_bytes(&bytes).unwrap();
+        assert_eq!(pod.as_raw_ptr() as *const u8, bytes.as_ptr());
+
+        assert!(Pod::from_bytes(&bytes[..4]).is_err());
+        assert!(Pod::from_bytes(&bytes[..10]).is_err());
+    }
+
+    #[test]
+    fn accessors() {
+        let bytes = int_pod_bytes();
+        let pod = <&Pod>::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(pod.type_(), Ok(SpaType::Int));
+        assert_eq!(pod.size(), 4);
+        assert_eq!(pod.as_bytes(), &bytes[..12]);
+        assert_eq!(pod.size_including_header(), 16);
+    }
+
+    #[test]
+    fn from_bytes_unchecked() {
+        let bytes = int_pod_bytes();
+
+        assert!(unsafe { Pod::from_bytes_unchecked(&bytes) }.is_some());
+        assert!(unsafe { Pod::from_bytes_unchecked(&bytes[..4]) }.is_none());
+    }
+
+    #[test]
+    fn validate() {
+        let value = Value::Struct(vec![
+            Value::Int(1),
+            Value::String("foo".to_string()),
+            Value::Object(Object {
+                type_: spa_sys::SPA_TYPE_OBJECT_Props,
+                id: spa_sys::SPA_PARAM_Props,
+                properties: vec![Property {
+                    key: spa_sys::SPA_PROP_volume,
+                    flags: PropertyFlags::empty(),
+                    value: Value::Float(0.5),
+                }],
+            }),
+            Value::ValueArray(ValueArray::Int(vec![1, 2, 3])),
+        ]);
+        let mut bytes = PodSerializer::serialize(Cursor::new(Vec::new()), &value)
+            .unwrap()
+            .0
+            .into_inner();
+
+        assert!(Pod::validate(&bytes).is_ok());
+        assert!(Pod::validate(&bytes[..bytes.len() - 8]).is_err());
+        assert!(Pod::validate(&[]).is_err());
+
+        // Remove the terminating null byte of the string
+        let pos = bytes.windows(4).position(|w| w == b"foo\0").unwrap();
+        bytes[pos + 3] = b'o';
+        assert!(Pod::validate(&bytes).is_err());
+
+        // Deeply nested structs are rejected instead of overflowing the stack
+        let nested = |depth| {
+            let value = (0..depth).fold(Value::None, |value, _| Value::Struct(vec![value]));
+            PodSerializer::serialize(Cursor::new(Vec::new()), &value)
+                .unwrap()
+                .0
+                .into_inner()
+        };
+        assert!(Pod::validate(&nested(MAX_DEPTH)).is_ok());
+        assert!(Pod::validate(&nested(MAX_DEPTH + 1)).is_err());
+    }
+
+    #[test]
+    fn owned() {
+        let bytes = int_pod_bytes();
+
+        let owned = PodOwned::from_bytes(&bytes[1..]);
+        assert!(owned.is_err());
+
+        let owned = PodOwned::from_bytes(&bytes).unwrap();
+        assert_eq!(owned.as_bytes(), &bytes[..]);
+        assert_eq!(owned.as_pod().as_raw_ptr() as usize % 8, 0);
+
+        let pod: &Pod = &owned;
+        let copy = pod.to_owned();
+        assert_eq!(copy.into_bytes(), bytes);
+    }
+}
@@ -0,0 +1,1148 @@
+//! This module provides the [`PodBuilder`], which can be used to incrementally build raw SPA pods.
+//!
+//! Unlike the [`serialize`](`super::serialize`) module, which serializes a complete rust value in one go,
+//! the builder allows appending pods one at a time into a growing buffer, opening and closing
+//! container pods such as structs with frames, similar to the `spa_pod_builder` C API.
+
+use std::{io::Cursor, marker::PhantomData};
+
+use cookie_factory::GenError;
+
+use super::{
+    serialize::{PodSerialize, PodSerializer},
+    CanonicalFixedSizedPod, FixedSizedPod, Pod, PodOwned, PropertyFlags,
+};
+use crate::{
+    result::Error,
+    utils::{ChoiceFlags, Fd, Fraction, Id, Rectangle, SpaType},
+};
+
+/// Size of a pod header, made of the body size and the pod type, both `u32`.
+const HEADER_SIZE: usize = 8;
+
+/// Size of the choice type and flags at the start of a choice pod body, both `u32`.
+const CHOICE_BODY_HEADER_SIZE: usize = 8;
+
+/// A builder for incrementally writing raw SPA pods into a buffer.
+///
+/// Pods are appended to the buffer using the various `push_*` methods.
+/// Container pods are built by opening a frame with a `begin_*` method, pushing their children,
+/// and then finishing them by passing the frame to the matching `end_*` method.
+///
+/// Frames must be ended in the reverse order they were opened in.
+/// The `open_*` and `close_*` methods are aliases of the `begin_*` and `end_*` methods,
+/// matching the names of the `spa_pod_builder` C API.
+///
+/// The buffer is either allocated by the builder, or supplied with [`with_buffer`](`Self::with_buffer`)
+/// so that its allocation can be reused. In both cases it grows as needed.
+///
+/// # Examples
+/// Build a `Struct` pod containing an `Int` and a `String`:
+/// ```rust
+/// use libspa::pod::builder::PodBuilder;
+///
+/// let mut builder = PodBuilder::new();
+/// let frame = builder.begin_struct();
+/// builder.push_int(42).unwrap();
+/// builder.push_string("foo").unwrap();
+/// builder.end_struct(frame);
+///
+/// let bytes = builder.into_inner();
+/// ```
+#[derive(Debug, Default)]
+pub struct PodBuilder {
+    data: Vec<u8>,
+}
+
+/// A frame of a `Struct` pod that is currently being built.
+///
+/// It is obtained by calling [`PodBuilder::begin_struct`] and must be passed to
+/// [`PodBuilder::end_struct`] once all fields have been pushed.
+#[derive(Debug)]
+#[must_use = "The struct must be finished by passing the frame to `PodBuilder::end_struct`"]
+pub struct StructFrame {
+    /// Offset of the struct pod header in the builder buffer.
+    offset: usize,
+}
+
+/// A frame of an `Object` pod that is currently being built.
+///
+/// It is obtained by calling [`PodBuilder::begin_object`] and must be passed to
+/// [`PodBuilder::end_object`] once all properties have been pushed.
+#[derive(Debug)]
+#[must_use = "The object must be finished by passing the frame to `PodBuilder::end_object`"]
+pub struct ObjectFrame {
+    /// Offset of the object pod header in the builder buffer.
+    offset: usize,
+}
+
+/// A frame of an `Array` pod that is currently being built.
+///
+/// It is obtained by calling [`PodBuilder::begin_array`].
+/// Elements are added with [`push_element`](`Self::push_element`), and the array is ended when the
+/// frame is finished with [`finish`](`Self::finish`) or dropped.
+///
+/// The frame borrows the builder, so no other pod can be pushed while the array is being built.
+#[must_use = "The array is ended as soon as the frame is dropped"]
+pub struct ArrayFrame<'b, T: FixedSizedPod> {
+    builder: &'b mut PodBuilder,
+    /// Offset of the array pod header in the builder buffer.
+    offset: usize,
+    /// The number of elements that have been written already
+    written: u32,
+    /// The frame has the type parameter T to ensure all elements are the same type,
+    /// but doesn't actually own any T, so we need the `PhantomData<T>` instead.
+    _phantom: PhantomData<T>,
+}
+
+impl<'b, T: FixedSizedPod> ArrayFrame<'b, T> {
+    /// Append a single element to the array.
+    pub fn push_element(&mut self, value: T) -> Result<(), GenError> {
+        value
+            .as_canonical_type()
+            .serialize_body(&mut self.builder.data)?;
+        self.written += 1;
+        Ok(())
+    }
+
+    /// End the array.
+    ///
+    /// This is equivalent to dropping the frame, but makes the end of the array explicit.
+    pub fn finish(self) {}
+}
+
+impl<'b, T: FixedSizedPod> Drop for ArrayFrame<'b, T> {
+    fn drop(&mut self) {
+        // Body is made of the child header followed by the elements.
+        let size = HEADER_SIZE + (self.written * T::CanonicalType::SIZE) as usize;
+        self.builder
+            .write_header(self.offset, size, spa_sys::SPA_TYPE_Array);
+        self.builder.pad();
+    }
+}
+
+/// A builder for a `Struct` pod, ending the struct when it is finished or dropped.
+///
+/// This is a safer alternative to [`PodBuilder::begin_struct`] and [`PodBuilder::end_struct`]:
+/// as it borrows the parent builder, no frame can be ended out of order.
+///
+/// # Examples
+/// ```rust
+/// use libspa::pod::builder::{PodBuilder, PodStructBuilder};
+///
+/// let mut builder = PodBuilder::new();
+/// let mut struct_ = PodStructBuilder::new(&mut builder);
+/// struct_.push_field(&42i32).unwrap();
+/// struct_.push_field("foo").unwrap();
+/// struct_.finish();
+/// ```
+#[must_use = "The struct is ended as soon as the builder is dropped"]
+pub struct PodStructBuilder<'b> {
+    builder: &'b mut PodBuilder,
+    frame: Option<StructFrame>,
+}
+
+impl<'b> PodStructBuilder<'b> {
+    /// Begin building a `Struct` pod in `parent`.
+    pub fn new(parent: &'b mut PodBuilder) -> Self {
+        let frame = parent.begin_struct();
+        Self {
+            builder: parent,
+            frame: Some(frame),
+        }
+    }
+
+    /// Append a field to the struct.
+    pub fn push_field<P>(&mut self, value: &P) -> Result<(), GenError>
+    where
+        P: PodSerialize + ?Sized,
+    {
+        self.builder.push_pod(value)
+    }
+
+    /// Begin building a `Struct` pod nested in this one.
+    ///
+    /// The nested struct must be finished before other fields can be pushed to this one.
+    pub fn push_struct(&mut self) -> PodStructBuilder<'_> {
+        PodStructBuilder::new(self.builder)
+    }
+
+    /// Finish building the struct.
+    ///
+    /// This is equivalent to dropping the builder.
+    pub fn finish(self) {}
+}
+
+impl<'b> Drop for PodStructBuilder<'b> {
+    fn drop(&mut self) {
+        if let Some(frame) = self.frame.take() {
+            self.builder.end_struct(frame);
+        }
+    }
+}
+
+/// A builder for an `Object` pod, ending the object when it is finished or dropped.
+///
+/// This is a safer alternative to [`PodBuilder::begin_object`] and [`PodBuilder::end_object`]:
+/// as it borrows the parent builder, no frame can be ended out of order,
+/// and each property is always followed by exactly one value.
+///
+/// # Examples
+/// ```rust
+/// use libspa::pod::{builder::{PodBuilder, PodObjectBuilder}, PropertyFlags};
+/// use libspa::utils::{Id, SpaType};
+///
+/// let mut builder = PodBuilder::new();
+/// let mut object = PodObjectBuilder::new(
+///     &mut builder,
+///     SpaType::ObjectFormat,
+///     libspa_sys::SPA_PARAM_EnumFormat,
+/// );
+/// object
+///     .push_prop(
+///         libspa_sys::SPA_FORMAT_mediaType,
+///         PropertyFlags::empty(),
+///         &Id(libspa_sys::SPA_MEDIA_TYPE_audio),
+///     )
+///     .unwrap();
+/// object.finish();
+/// ```
+#[must_use = "The object is ended as soon as the builder is dropped"]
+pub struct PodObjectBuilder<'b> {
+    builder: &'b mut PodBuilder,
+    frame: Option<ObjectFrame>,
+}
+
+impl<'b> PodObjectBuilder<'b> {
+    /// Begin building an `Object` pod of the provided object type and id in `parent`,
+    /// such as [`SpaType::ObjectFormat`] and `SPA_PARAM_EnumFormat`.
+    pub fn new(parent: &'b mut PodBuilder, type_: SpaType, id: u32) -> Self {
+        let frame = parent.begin_object(type_.as_raw(), id);
+        Self {
+            builder: parent,
+            frame: Some(frame),
+        }
+    }
+
+    /// Append a property with the provided key, flags and value to the object.
+    pub fn push_prop<P>(
+        &mut self,
+        key: u32,
+        flags: PropertyFlags,
+        value: &P,
+    ) -> Result<(), GenError>
+    where
+        P: PodSerialize + ?Sized,
+    {
+        self.builder.push_property(key, flags);
+        self.builder.push_pod(value)
+    }
+
+    /// Append a property whose value is a `Struct` pod, returning the builder of the struct.
+    ///
+    /// The struct must be finished before other properties can be pushed to the object.
+    pub fn push_prop_struct(&mut self, key: u32, flags: PropertyFlags) -> PodStructBuilder<'_> {
+        self.builder.push_property(key, flags);
+        PodStructBuilder::new(self.builder)
+    }
+
+    /// Finish building the object.
+    ///
+    /// This is equivalent to dropping the builder.
+    pub fn finish(self) {}
+}
+
+impl<'b> Drop for PodObjectBuilder<'b> {
+    fn drop(&mut self) {
+        if let Some(frame) = self.frame.take() {
+            self.builder.end_object(frame);
+        }
+    }
+}
+
+/// A builder for a `Choice` pod whose values are of type `T`, ending the choice when it is finished or dropped.
+///
+/// The first value pushed to the choice must be its default value, pushed with
+/// [`push_default`](Self::push_default), followed by the other values of the choice,
+/// pushed with [`push_alternative`](Self::push_alternative):
+/// the alternatives of an `Enum` choice, the minimum and maximum of a `Range` choice,
+/// or the possible flags of a `Flags` choice.
+///
+/// A choice without any value is invalid, so nothing is written if the builder is dropped
+/// before the default value was pushed, and [`finish`](Self::finish) returns an error.
+///
+/// # Panics
+/// Pushing values in the wrong order panics: [`push_default`](Self::push_default) must be called
+/// exactly once, before [`push_alternative`](Self::push_alternative).
+///
+/// # Examples
+/// ```rust
+/// use libspa::pod::builder::{PodBuilder, PodChoiceBuilder};
+///
+/// let mut builder = PodBuilder::new();
+/// let mut choice = PodChoiceBuilder::new_range(&mut builder);
+/// choice.push_default(48000i32).unwrap();
+/// choice.push_alternative(1).unwrap();
+/// choice.push_alternative(i32::MAX).unwrap();
+/// choice.finish().unwrap();
+/// ```
+#[must_use = "The choice is ended as soon as the builder is dropped"]
+pub struct PodChoiceBuilder<'b, T: FixedSizedPod> {
+    builder: &'b mut PodBuilder,
+    /// Offset of the choice pod header in the builder buffer.
+    offset: usize,
+    /// The number of values that have been written already, including the default value.
+    written: u32,
+    _phantom: PhantomData<T>,
+}
+
+impl<'b, T: FixedSizedPod> PodChoiceBuilder<'b, T> {
+    fn new(parent: &'b mut PodBuilder, choice_type: u32) -> Self {
+        let offset = parent.data.len();
+        // Write a size of 0 for now, this will be updated when the builder is dropped.
+        parent.write_header(offset, 0, spa_sys::SPA_TYPE_Choice);
+        parent.data.extend_from_slice(&choice_type.to_ne_bytes());
+        parent
+            .data
+            .extend_from_slice(&ChoiceFlags::empty().bits().to_ne_bytes());
+        parent.write_header(
+            offset + CHOICE_BODY_HEADER_SIZE,
+            T::CanonicalType::SIZE as usize,
+            T::CanonicalType::TYPE,
+        );
+
+        Self {
+            builder: parent,
+            offset,
+            written: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Begin building an `Enum` choice in `parent`, whose alternatives are a list of possible values.
+    pub fn new_enum(parent: &'b mut PodBuilder) -> Self {
+        Self::new(parent, spa_sys::SPA_CHOICE_Enum)
+    }
+
+    /// Begin building a `Range` choice in `parent`, whose alternatives are the minimum and maximum values.
+    pub fn new_range(parent: &'b mut PodBuilder) -> Self {
+        Self::new(parent, spa_sys::SPA_CHOICE_Range)
+    }
+
+    /// Begin building a `Flags` choice in `parent`, whose alternatives are the possible flags.
+    pub fn new_flags(parent: &'b mut PodBuilder) -> Self {
+        Self::new(parent, spa_sys::SPA_CHOICE_Flags)
+    }
+
+    /// Append the default value of the choice.
+    ///
+    /// # Panics
+    /// If a value has already been pushed to the choice.
+    pub fn push_default(&mut self, value: T) -> Result<(), GenError> {
+        assert_eq!(self.written, 0, "The default value must be pushed first");
+        self.push_value(value)
+    }
+
+    /// Append an alternative value to the choice.
+    ///
+    /// # Panics
+    /// If the default value has not been pushed yet.
+    pub fn push_alternative(&mut self, value: T) -> Result<(), GenError> {
+        assert_ne!(
+            self.written, 0,
+            "The default value must be pushed before alternatives"
+        );
+        self.push_value(value)
+    }
+
+    fn push_value(&mut self, value: T) -> Result<(), GenError> {
+        value
+            .as_canonical_type()
+            .serialize_body(&mut self.builder.data)?;
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Finish building the choice.
+    ///
+    /// # Errors
+    /// Returns an `EINVAL` error if no default value was pushed, in which case the choice is not written.
+    pub fn finish(self) -> Result<(), Error> {
+        if self.written == 0 {
+            return Err(Error::new(libc::EINVAL));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'b, T: FixedSizedPod> Drop for PodChoiceBuilder<'b, T> {
+    fn drop(&mut self) {
+        if self.written == 0 {
+            // Remove the headers of the choice, as a choice must hold at least one value.
+            self.builder.data.truncate(self.offset);
+            return;
+        }
+
+        // Body is made of the choice type and flags, the child header and the values.
+        let size = CHOICE_BODY_HEADER_SIZE
+            + HEADER_SIZE
+            + (self.written * T::CanonicalType::SIZE) as usize;
+        self.builder
+            .write_header(self.offset, size, spa_sys::SPA_TYPE_Choice);
+        self.builder.pad();
+    }
+}
+
+/// A builder for a `Sequence` pod, ending the sequence when it is finished or dropped.
+///
+/// A sequence is made of controls, such as MIDI events or property changes, each holding
+/// a single pod and happening at an offset in the unit of the sequence.
+///
+/// # Examples
+/// ```rust
+/// use libspa::pod::builder::{PodBuilder, PodSequenceBuilder};
+///
+/// let mut builder = PodBuilder::new();
+/// let mut sequence = PodSequenceBuilder::new(&mut builder, 0);
+/// sequence
+///     .push_control(0, libspa_sys::SPA_CONTROL_Midi, |builder| {
+///         builder.push_bytes(&[0x90, 0x40, 0x7f])
+///     })
+///     .unwrap();
+/// sequence.finish();
+/// ```
+#[must_use = "The sequence is ended as soon as the builder is dropped"]
+pub struct PodSequenceBuilder<'b> {
+    builder: &'b mut PodBuilder,
+    /// Offset of the sequence pod header in the builder buffer.
+    offset: usize,
+}
+
+impl<'b> PodSequenceBuilder<'b> {
+    /// Begin building a `Sequence` pod in `parent`, whose control offsets are in `unit`.
+    pub fn new(parent: &'b mut PodBuilder, unit: u32) -> Self {
+        let offset = parent.data.len();
+        // Write a size of 0 for now, this will be updated when the builder is dropped.
+        parent.write_header(offset, 0, spa_sys::SPA_TYPE_Sequence);
+        parent.data.extend_from_slice(&unit.to_ne_bytes());
+        // Padding of the sequence body.
+        parent.data.extend_from_slice(&0u32.to_ne_bytes());
+
+        Self {
+            builder: parent,
+            offset,
+        }
+    }
+
+    /// Append a control of the provided type, such as `SPA_CONTROL_Midi`, at `offset` in the sequence.
+    ///
+    /// The value of the control is the pod pushed to the builder by `f`,
+    /// which should push exactly one pod.
+    pub fn push_control<F>(&mut self, offset: u32, type_: u32, f: F) -> Result<(), GenError>
+    where
+        F: FnOnce(&mut PodBuilder) -> Result<(), GenError>,
+    {
+        self.builder.data.extend_from_slice(&offset.to_ne_bytes());
+        self.builder.data.extend_from_slice(&type_.to_ne_bytes());
+        f(self.builder)?;
+        self.builder.pad();
+        Ok(())
+    }
+
+    /// Finish building the sequence.
+    ///
+    /// This is equivalent to dropping the builder.
+    pub fn finish(self) {}
+}
+
+impl<'b> Drop for PodSequenceBuilder<'b> {
+    fn drop(&mut self) {
+        // No padding needed: Each control already ends aligned.
+        let size = self.builder.data.len() - self.offset - HEADER_SIZE;
+        self.builder
+            .write_header(self.offset, size, spa_sys::SPA_TYPE_Sequence);
+    }
+}
+
+impl PodBuilder {
+    /// Create a new builder with an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new builder with an empty buffer able to hold at least `capacity` bytes
+    /// before reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Create a new builder writing into `buffer`, reusing its allocation.
+    ///
+    /// The previous content of `buffer` is discarded.
+    /// The buffer can be retrieved with [`into_inner`](`Self::into_inner`) once the pods are built.
+    pub fn with_buffer(mut buffer: Vec<u8>) -> Self {
+        buffer.clear();
+        Self { data: buffer }
+    }
+
+    /// Get the bytes that have been written so far.
+    ///
+    /// If a frame is still open, the size of the corresponding container pod is not yet correct.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get the current byte offset in the buffer, where the next pod will be written.
+    ///
+    /// This can be used to record where each pod starts when writing several pods one after the other,
+    /// to later get them from [`as_bytes`](`Self::as_bytes`).
+    pub fn write_position(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Consume the builder, returning the buffer holding the written pods.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Consume the builder, returning the first pod that was written as a [`PodOwned`].
+    ///
+    /// # Panics
+    /// If no pod has been written.
+    pub fn build_owned(self) -> PodOwned {
+        self.build()
+            .expect("No pod has been written to the builder")
+    }
+
+    /// Consume the builder, returning the first pod that was written as a [`PodOwned`].
+    ///
+    /// # Errors
+    /// Returns an `EINVAL` error if no complete pod has been written.
+    pub fn build(self) -> Result<PodOwned, Error> {
+        PodOwned::from_bytes(&self.data)
+    }
+
+    /// Discard everything that has been written so far, so that a new pod can be built.
+    ///
+    /// The buffer keeps its allocated memory, so reusing the builder does not need to allocate again.
+    pub fn reset(&mut self) {
+        self.data.clear();
+    }
+
+    /// Append any pod that implements [`PodSerialize`] to the buffer.
+    pub fn push_pod<P>(&mut self, pod: &P) -> Result<(), GenError>
+    where
+        P: PodSerialize + ?Sized,
+    {
+        let mut cursor = Cursor::new(&mut self.data);
+        cursor.set_position(cursor.get_ref().len() as u64);
+        PodSerializer::serialize(cursor, pod)?;
+        Ok(())
+    }
+
+    /// Append a copy of an existing pod, such as one received in a `param_changed` callback.
+    ///
+    /// The raw bytes of `pod` are copied verbatim, padded so that the next pod is aligned.
+    pub fn clone_from_pod(&mut self, pod: &Pod) {
+        self.data.extend_from_slice(pod.as_bytes());
+        self.pad();
+    }
+
+    /// Append a `None` pod.
+    pub fn push_none(&mut self) -> Result<(), GenError> {
+        self.push_pod(&())
+    }
+
+    /// Append a `Bool` pod.
+    pub fn push_bool(&mut self, value: bool) -> Result<(), GenError> {
+        self.push_pod(&value)
+    }
+
+    /// Append an `Id` pod.
+    pub fn push_id(&mut self, value: Id) -> Result<(), GenError> {
+        self.push_pod(&value)
+    }
+
+    /// Append an `Int` pod.
+    pub fn push_int(&mut self, value: i32) -> Result<(), GenError> {
+        self.push_pod(&value)
+    }
+
+    /// Append a `Long` pod.
+    pub fn push_long(&mut self, value: i64) -> Result<(), GenError> {
+        self.push_pod(&value)
+    }
+
+    /// Append a `Float` pod.
+    pub fn push_float(&mut self, value: f32) -> Result<(), GenError> {
+        self.push_pod(&value)
+    }
+
+    /// Append a `Double` pod.
+    pub fn push_double(&mut self, value: f64) -> Result<(), GenError> {
+        self.push_pod(&value)
+    }
+
+    /// Append a `String` pod.
+    ///
+    /// # Panics
+    /// If `value` contains a null byte.
+    pub fn push_string(&mut self, value: &str) -> Result<(), GenError> {
+        self.push_pod(value)
+    }
+
+    /// Append a `Bytes` pod.
+    pub fn push_bytes(&mut self, value: &[u8]) -> Result<(), GenError> {
+        self.push_pod(value)
+    }
+
+    /// Append a `Rectangle` pod.
+    pub fn push_rectangle(&mut self, value: Rectangle) -> Result<(), GenError> {
+        self.push_pod(&value)
+    }
+
+    /// Append a `Rectangle` pod.
+    ///
+    /// This is an alias of [`push_rectangle`](`Self::push_rectangle`).
+    pub fn push_rect(&mut self, value: Rectangle) -> Result<(), GenError> {
+        self.push_rectangle(value)
+    }
+
+    /// Append a `Fraction` pod.
+    pub fn push_fraction(&mut self, value: Fraction) -> Result<(), GenError> {
+        self.push_pod(&value)
+    }
+
+    /// Append a `Fd` pod.
+    pub fn push_fd(&mut self, value: Fd) -> Result<(), GenError> {
+        self.push_pod(&value)
+    }
+
+    /// Write a pod header with the provided body size and type at `offset`,
+    /// which may either point into the buffer or at its end.
+    fn write_header(&mut self, offset: usize, size: usize, type_: u32) {
+        let mut header = [0u8; HEADER_SIZE];
+        header[..4].copy_from_slice(&(size as u32).to_ne_bytes());
+        header[4..].copy_from_slice(&type_.to_ne_bytes());
+
+        if offset == self.data.len() {
+            self.data.extend_from_slice(&header);
+        } else {
+            self.data[offset..offset + HEADER_SIZE].copy_from_slice(&header);
+        }
+    }
+
+    /// Begin building a `Struct` pod.
+    ///
+    /// Any pod pushed to the builder after this call becomes a field of the struct,
+    /// until the returned frame is passed to [`end_struct`](`Self::end_struct`).
+    pub fn begin_struct(&mut self) -> StructFrame {
+        let offset = self.data.len();
+        // Write a size of 0 for now, this will be updated when calling `end_struct()`.
+        self.write_header(offset, 0, spa_sys::SPA_TYPE_Struct);
+
+        StructFrame { offset }
+    }
+
+    /// Finish building the `Struct` pod that was started with the provided frame.
+    pub fn end_struct(&mut self, frame: StructFrame) {
+        // No padding needed: Each field already ends aligned.
+        let size = self.data.len() - frame.offset - HEADER_SIZE;
+        self.write_header(frame.offset, size, spa_sys::SPA_TYPE_Struct);
+    }
+
+    /// Begin building an `Object` pod of the provided object type and id,
+    /// such as `SPA_TYPE_OBJECT_Format` and `SPA_PARAM_EnumFormat`.
+    ///
+    /// Properties are added by calling [`push_property`](`Self::push_property`)
+    /// followed by pushing the value of the property,
+    /// until the returned frame is passed to [`end_object`](`Self::end_object`).
+    pub fn begin_object(&mut self, type_: u32, id: u32) -> ObjectFrame {
+        let offset = self.data.len();
+        // Write a size of 0 for now, this will be updated when calling `end_object()`.
+        self.write_header(offset, 0, spa_sys::SPA_TYPE_Object);
+        self.data.extend_from_slice(&type_.to_ne_bytes());
+        self.data.extend_from_slice(&id.to_ne_bytes());
+
+        ObjectFrame { offset }
+    }
+
+    /// Begin a property of the object being built.
+    ///
+    /// The next pod pushed to the builder is the value of the property.
+    pub fn push_property(&mut self, key: u32, flags: PropertyFlags) {
+        self.data.extend_from_slice(&key.to_ne_bytes());
+        self.data.extend_from_slice(&flags.bits().to_ne_bytes());
+    }
+
+    /// Finish building the `Object` pod that was started with the provided frame.
+    pub fn end_object(&mut self, frame: ObjectFrame) {
+        // No padding needed: Each property value already ends aligned.
+        let size = self.data.len() - frame.offset - HEADER_SIZE;
+        self.write_header(frame.offset, size, spa_sys::SPA_TYPE_Object);
+    }
+
+    /// Begin building an `Array` pod whose elements are of type `T`.
+    ///
+    /// Elements are appended using [`ArrayFrame::push_element`] on the returned frame,
+    /// and the array is ended once the frame is finished or dropped.
+    pub fn begin_array<T: FixedSizedPod>(&mut self) -> ArrayFrame<'_, T> {
+        let offset = self.data.len();
+        // Write a size of 0 for now, this will be updated when the frame is dropped.
+        self.write_header(offset, 0, spa_sys::SPA_TYPE_Array);
+        self.write_header(
+            offset + HEADER_SIZE,
+            T::CanonicalType::SIZE as usize,
+            T::CanonicalType::TYPE,
+        );
+
+        ArrayFrame {
+            builder: self,
+            offset,
+            written: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Finish building the `Array` pod that was started with the provided frame.
+    ///
+    /// As the frame borrows the builder, this is not a method of the builder,
+    /// but the same as calling [`ArrayFrame::finish`].
+    pub fn end_array<T: FixedSizedPod>(frame: ArrayFrame<'_, T>) {
+        frame.finish();
+    }
+
+    /// Begin building a `Struct` pod.
+    ///
+    /// This is an alias of [`begin_struct`](`Self::begin_struct`).
+    pub fn open_struct(&mut self) -> StructFrame {
+        self.begin_struct()
+    }
+
+    /// Finish building a `Struct` pod.
+    ///
+    /// This is an alias of [`end_struct`](`Self::end_struct`).
+    pub fn close_struct(&mut self, frame: StructFrame) {
+        self.end_struct(frame)
+    }
+
+    /// Begin building an `Object` pod.
+    ///
+    /// This is an alias of [`begin_object`](`Self::begin_object`).
+    pub fn open_object(&mut self, type_: u32, id: u32) -> ObjectFrame {
+        self.begin_object(type_, id)
+    }
+
+    /// Finish building an `Object` pod.
+    ///
+    /// This is an alias of [`end_object`](`Self::end_object`).
+    pub fn close_object(&mut self, frame: ObjectFrame) {
+        self.end_object(frame)
+    }
+
+    /// Begin building an `Array` pod.
+    ///
+    /// This is an alias of [`begin_array`](`Self::begin_array`).
+    pub fn open_array<T: FixedSizedPod>(&mut self) -> ArrayFrame<'_, T> {
+        self.begin_array()
+    }
+
+    /// Finish building an `Array` pod.
+    ///
+    /// This is an alias of [`end_array`](`Self::end_array`).
+    pub fn close_array<T: FixedSizedPod>(frame: ArrayFrame<'_, T>) {
+        frame.finish();
+    }
+
+    /// Add padding to the buffer, so that the next pod is aligned to 8 bytes.
+    fn pad(&mut self) {
+        let len = self.data.len();
+        if len % 8 != 0 {
+            self.data.resize(len + 8 - (len % 8), 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{
+        PodBuilder, PodChoiceBuilder, PodObjectBuilder, PodSequenceBuilder, PodStructBuilder,
+    };
+    use crate::pod::{serialize::PodSerializer, Object, Pod, Property, PropertyFlags, Value};
+    use crate::pod::{ChoiceValue, ValueArray};
+    use crate::utils::{Choice, ChoiceEnum, ChoiceFlags, Id, Rectangle, SpaType};
+
+    fn serialize(value: &Value) -> Vec<u8> {
+        PodSerializer::serialize(Cursor::new(Vec::new()), value)
+            .unwrap()
+            .0
+            .into_inner()
+    }
+
+    #[test]
+    fn struct_() {
+        let mut builder = PodBuilder::new();
+        let frame = builder.begin_struct();
+        builder.push_int(3).unwrap();
+        builder.push_string("foo").unwrap();
+        builder
+            .push_rectangle(Rectangle {
+                width: 4,
+                height: 5,
+            })
+            .unwrap();
+        builder.end_struct(frame);
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::Struct(vec![
+                Value::Int(3),
+                Value::String("foo".into()),
+                Value::Rectangle(Rectangle {
+                    width: 4,
+                    height: 5,
+                }),
+            ]))
+        );
+    }
+
+    #[test]
+    fn nested_struct() {
+        let mut builder = PodBuilder::new();
+        let outer = builder.begin_struct();
+        builder.push_bool(true).unwrap();
+        let inner = builder.begin_struct();
+        builder.push_long(7).unwrap();
+        builder.end_struct(inner);
+        builder.push_none().unwrap();
+        builder.end_struct(outer);
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::Struct(vec![
+                Value::Bool(true),
+                Value::Struct(vec![Value::Long(7)]),
+                Value::None,
+            ]))
+        );
+    }
+
+    #[test]
+    fn struct_builder() {
+        let mut builder = PodBuilder::new();
+        let mut outer = PodStructBuilder::new(&mut builder);
+        outer.push_field(&true).unwrap();
+        let mut inner = outer.push_struct();
+        inner.push_field(&7i64).unwrap();
+        inner.finish();
+        outer.push_field("foo").unwrap();
+        drop(outer);
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::Struct(vec![
+                Value::Bool(true),
+                Value::Struct(vec![Value::Long(7)]),
+                Value::String("foo".into()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn array() {
+        let mut builder = PodBuilder::new();
+        let mut frame = builder.begin_array();
+        for i in 1..=3 {
+            frame.push_element(i).unwrap();
+        }
+        PodBuilder::end_array(frame);
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::ValueArray(ValueArray::Int(vec![1, 2, 3])))
+        );
+    }
+
+    #[test]
+    fn array_dropped() {
+        let mut builder = PodBuilder::new();
+        {
+            let mut frame = builder.begin_array();
+            frame.push_element(1.5f32).unwrap();
+        }
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::ValueArray(ValueArray::Float(vec![1.5])))
+        );
+    }
+
+    #[test]
+    fn array_in_struct() {
+        let mut builder = PodBuilder::new();
+        let outer = builder.begin_struct();
+        let mut frame = builder.begin_array();
+        frame.push_element(Id(4)).unwrap();
+        frame.finish();
+        builder.push_int(5).unwrap();
+        builder.end_struct(outer);
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::Struct(vec![
+                Value::ValueArray(ValueArray::Id(vec![Id(4)])),
+                Value::Int(5),
+            ]))
+        );
+    }
+
+    #[test]
+    fn reset() {
+        let mut builder = PodBuilder::new();
+        builder.push_string("foobar").unwrap();
+        builder.reset();
+        assert!(builder.as_bytes().is_empty());
+
+        builder.push_int(1).unwrap();
+        assert_eq!(builder.into_inner(), serialize(&Value::Int(1)));
+    }
+
+    #[test]
+    fn clone_from_pod() {
+        let bytes = serialize(&Value::Struct(vec![
+            Value::Int(1),
+            Value::String("foo".into()),
+        ]));
+        let pod = Pod::from_bytes(&bytes).unwrap();
+
+        let mut builder = PodBuilder::new();
+        let frame = builder.begin_struct();
+        builder.clone_from_pod(pod);
+        builder.push_bool(false).unwrap();
+        builder.end_struct(frame);
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::Struct(vec![
+                Value::Struct(vec![Value::Int(1), Value::String("foo".into())]),
+                Value::Bool(false),
+            ]))
+        );
+    }
+
+    #[test]
+    fn write_position() {
+        let mut builder = PodBuilder::new();
+        assert_eq!(builder.write_position(), 0);
+        builder.push_int(1).unwrap();
+
+        let start = builder.write_position();
+        builder.push_string("foo").unwrap();
+        let end = builder.write_position();
+
+        assert_eq!(
+            &builder.as_bytes()[start..end],
+            &serialize(&Value::String("foo".into()))[..]
+        );
+    }
+
+    #[test]
+    fn build_owned() {
+        let mut builder = PodBuilder::with_capacity(64);
+        builder.push_long(8).unwrap();
+
+        assert_eq!(builder.build_owned().as_bytes(), serialize(&Value::Long(8)));
+    }
+
+    #[test]
+    fn build() {
+        let mut builder = PodBuilder::with_buffer(vec![1, 2, 3]);
+        let outer = builder.open_struct();
+        let mut frame = builder.open_array();
+        frame.push_element(2i64).unwrap();
+        PodBuilder::close_array(frame);
+        builder
+            .push_rect(Rectangle {
+                width: 1,
+                height: 2,
+            })
+            .unwrap();
+        builder.close_struct(outer);
+
+        assert_eq!(
+            builder.build().unwrap().as_bytes(),
+            serialize(&Value::Struct(vec![
+                Value::ValueArray(ValueArray::Long(vec![2])),
+                Value::Rectangle(Rectangle {
+                    width: 1,
+                    height: 2,
+                }),
+            ]))
+        );
+        assert!(PodBuilder::new().build().is_err());
+    }
+
+    #[test]
+    fn object() {
+        let mut builder = PodBuilder::new();
+        let frame = builder.begin_object(
+            spa_sys::SPA_TYPE_OBJECT_Format,
+            spa_sys::SPA_PARAM_EnumFormat,
+        );
+        builder.push_property(spa_sys::SPA_FORMAT_mediaType, PropertyFlags::empty());
+        builder.push_id(Id(spa_sys::SPA_MEDIA_TYPE_audio)).unwrap();
+        builder.push_property(spa_sys::SPA_FORMAT_AUDIO_rate, PropertyFlags::HARDWARE);
+        builder.push_int(48000).unwrap();
+        builder.end_object(frame);
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::Object(Object {
+                type_: spa_sys::SPA_TYPE_OBJECT_Format,
+                id: spa_sys::SPA_PARAM_EnumFormat,
+                properties: vec![
+                    Property {
+                        key: spa_sys::SPA_FORMAT_mediaType,
+                        flags: PropertyFlags::empty(),
+                        value: Value::Id(Id(spa_sys::SPA_MEDIA_TYPE_audio)),
+                    },
+                    Property {
+                        key: spa_sys::SPA_FORMAT_AUDIO_rate,
+                        flags: PropertyFlags::HARDWARE,
+                        value: Value::Int(48000),
+                    },
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn object_builder() {
+        let mut builder = PodBuilder::new();
+        let mut object = PodObjectBuilder::new(
+            &mut builder,
+            SpaType::ObjectFormat,
+            spa_sys::SPA_PARAM_EnumFormat,
+        );
+        object
+            .push_prop(
+                spa_sys::SPA_FORMAT_mediaType,
+                PropertyFlags::empty(),
+                &Id(spa_sys::SPA_MEDIA_TYPE_audio),
+            )
+            .unwrap();
+        let mut struct_ =
+            object.push_prop_struct(spa_sys::SPA_FORMAT_AUDIO_rate, PropertyFlags::HARDWARE);
+        struct_.push_field(&48000i32).unwrap();
+        struct_.finish();
+        object.finish();
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::Object(Object {
+                type_: spa_sys::SPA_TYPE_OBJECT_Format,
+                id: spa_sys::SPA_PARAM_EnumFormat,
+                properties: vec![
+                    Property {
+                        key: spa_sys::SPA_FORMAT_mediaType,
+                        flags: PropertyFlags::empty(),
+                        value: Value::Id(Id(spa_sys::SPA_MEDIA_TYPE_audio)),
+                    },
+                    Property {
+                        key: spa_sys::SPA_FORMAT_AUDIO_rate,
+                        flags: PropertyFlags::HARDWARE,
+                        value: Value::Struct(vec![Value::Int(48000)]),
+                    },
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn choice_builder() {
+        let mut builder = PodBuilder::new();
+        let mut choice = PodChoiceBuilder::new_enum(&mut builder);
+        choice.push_default(Id(2)).unwrap();
+        choice.push_alternative(Id(1)).unwrap();
+        choice.push_alternative(Id(2)).unwrap();
+        choice.finish().unwrap();
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::Choice(ChoiceValue::Id(Choice(
+                ChoiceFlags::empty(),
+                ChoiceEnum::Enum {
+                    default: Id(2),
+                    alternatives: vec![Id(1), Id(2)],
+                }
+            ))))
+        );
+
+        let mut builder = PodBuilder::new();
+        let mut choice = PodChoiceBuilder::new_range(&mut builder);
+        choice.push_default(48000i32).unwrap();
+        choice.push_alternative(1).unwrap();
+        choice.push_alternative(i32::MAX).unwrap();
+        choice.finish().unwrap();
+
+        assert_eq!(
+            builder.into_inner(),
+            serialize(&Value::Choice(ChoiceValue::Int(Choice(
+                ChoiceFlags::empty(),
+                ChoiceEnum::Range {
+                    default: 48000,
+                    min: 1,
+                    max: i32::MAX,
+                }
+            ))))
+        );
+    }
+
+    #[test]
+    fn choice_builder_empty() {
+        let mut builder = PodBuilder::new();
+        builder.push_int(1).unwrap();
+        let choice = PodChoiceBuilder::<i32>::new_enum(&mut builder);
+        assert!(choice.finish().is_err());
+
+        assert_eq!(builder.into_inner(), serialize(&Value::Int(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn choice_builder_alternative_before_default() {
+        let mut builder = PodBuilder::new();
+        let mut choice = PodChoiceBuilder::new_enum(&mut builder);
+        let _ = choice.push_alternative(1i32);
+    }
+
+    #[test]
+    fn sequence_builder() {
+        let mut builder = PodBuilder::new();
+        let mut sequence = PodSequenceBuilder::new(&mut builder, 0);
+        sequence
+            .push_control(0, spa_sys::SPA_CONTROL_Midi, |builder| {
+                builder.push_bytes(&[0x90, 0x40, 0x7f])
+            })
+            .unwrap();
+        sequence
+            .push_control(16, spa_sys::SPA_CONTROL_Midi, |builder| {
+                builder.push_bytes(&[0x80, 0x40, 0x00])
+            })
+            .unwrap();
+        sequence.finish();
+
+        let bytes = builder.into_inner();
+        // Sequence header, body, then two controls, each made of offset, type and a padded bytes pod.
+        assert_eq!(bytes.len(), 8 + 8 + 2 * (8 + 8 + 8));
+        assert_eq!(&bytes[..4], &((bytes.len() - 8) as u32).to_ne_bytes());
+        assert_eq!(&bytes[4..8], &spa_sys::SPA_TYPE_Sequence.to_ne_bytes());
+        assert_eq!(&bytes[40..44], &16u32.to_ne_bytes());
+        assert_eq!(&bytes[56..59], &[0x80, 0x40, 0x00]);
+    }
+}
@@ -0,0 +1,429 @@
+//! This module provides the [`PodParser`], which can be used to read raw SPA pods one at a time.
+//!
+//! Unlike the [`deserialize`](`super::deserialize`) module, which deserializes a complete pod into a rust type,
+//! the parser works like a cursor moving over a sequence of pods, entering container pods such as structs
+//! and objects on demand, similar to the `spa_pod_parser` C API.
+
+use std::{convert::TryFrom, marker::PhantomData, mem};
+
+use super::{read_u32, CanonicalFixedSizedPod, FixedSizedPod, Pod, PropertyFlags};
+use crate::result::Error;
+use crate::utils::{Fd, Fraction, Id, Rectangle, SpaType};
+
+/// The type of the pods `P` is serialized into.
+fn canonical_type<P: FixedSizedPod>() -> SpaType {
+    SpaType::try_from(P::CanonicalType::TYPE).expect("Canonical fixed sized pods have a known type")
+}
+
+/// Size of a pod header, made of the body size and the pod type, both `u32`.
+const HEADER_SIZE: usize = 8;
+
+/// The error returned when there is no pod left to parse, as in the C `spa_pod_parser`.
+fn end_of_input() -> Error {
+    Error::new(libc::EPIPE)
+}
+
+/// The error returned when a pod does not have the requested type or is malformed.
+fn invalid() -> Error {
+    Error::new(libc::EINVAL)
+}
+
+/// A cursor over a sequence of raw pods.
+///
+/// Each `get_*` method parses the next pod, checking that it has the requested type,
+/// and moves the cursor to the pod following it.
+///
+/// Like the C `spa_pod_parser`, an `EPIPE` error is returned when there is no pod left to parse,
+/// and an `EINVAL` error when the next pod does not have the requested type or is malformed.
+/// In both cases, the cursor does not move.
+///
+/// # Examples
+/// Parse a `Struct` pod containing an `Int` and a `String`:
+/// ```rust
+/// use libspa::pod::{builder::PodBuilder, parser::PodParser};
+///
+/// let mut builder = PodBuilder::new();
+/// let frame = builder.begin_struct();
+/// builder.push_int(42).unwrap();
+/// builder.push_string("foo").unwrap();
+/// builder.end_struct(frame);
+/// let pod = builder.build_owned();
+///
+/// let mut parser = PodParser::new(&pod);
+/// let mut fields = parser.enter_struct().unwrap();
+/// assert_eq!(fields.get_int(), Ok(42));
+/// assert_eq!(fields.get_string(), Ok("foo"));
+/// assert!(fields.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PodParser<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> PodParser<'a> {
+    /// Create a parser whose only pod is `pod`.
+    pub fn new(pod: &'a Pod) -> Self {
+        Self {
+            input: pod.as_bytes(),
+        }
+    }
+
+    /// Create a parser over the raw pods contained in `bytes`.
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        Self { input: bytes }
+    }
+
+    /// Whether all pods have been parsed.
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// The raw `SPA_TYPE_*` type of the next pod, without moving the cursor.
+    pub fn peek_type(&self) -> Result<u32, Error> {
+        if self.input.is_empty() {
+            return Err(end_of_input());
+        }
+
+        read_u32(self.input, 4).ok_or_else(invalid)
+    }
+
+    /// Check that the next pod is of type `expected`, returning its body and the input following the pod.
+    fn next_pod(&self, expected: SpaType) -> Result<(&'a [u8], &'a [u8]), Error> {
+        let found = self.peek_type()?;
+        if found != expected.as_raw() {
+            return Err(invalid());
+        }
+
+        let size = read_u32(self.input, 0).ok_or_else(invalid)? as usize;
+        let end = HEADER_SIZE
+            .checked_add(size)
+            .filter(|end| *end <= self.input.len())
+            .ok_or_else(invalid)?;
+        // The last pod does not need to be padded.
+        let next = ((end + 7) & !7).min(self.input.len());
+
+        Ok((&self.input[HEADER_SIZE..end], &self.input[next..]))
+    }
+
+    /// Parse the next pod, which must be a fixed sized pod of type `P`.
+    fn get_fixed<P: FixedSizedPod>(&mut self) -> Result<P, Error> {
+        let (body, next) = self.next_pod(canonical_type::<P>())?;
+        let (_, value) = P::CanonicalType::deserialize_body(body).map_err(|_| invalid())?;
+        self.input = next;
+
+        Ok(P::from_canonical_type(&value))
+    }
+
+    /// Parse a `None` pod.
+    pub fn get_none(&mut self) -> Result<(), Error> {
+        self.get_fixed()
+    }
+
+    /// Parse a `Bool` pod.
+    pub fn get_bool(&mut self) -> Result<bool, Error> {
+        self.get_fixed()
+    }
+
+    /// Parse an `Id` pod.
+    pub fn get_id(&mut self) -> Result<Id, Error> {
+        self.get_fixed()
+    }
+
+    /// Parse an `Int` pod.
+    pub fn get_int(&mut self) -> Result<i32, Error> {
+        self.get_fixed()
+    }
+
+    /// Parse a `Long` pod.
+    pub fn get_long(&mut self) -> Result<i64, Error> {
+        self.get_fixed()
+    }
+
+    /// Parse a `Float` pod.
+    pub fn get_float(&mut self) -> Result<f32, Error> {
+        self.get_fixed()
+    }
+
+    /// Parse a `Double` pod.
+    pub fn get_double(&mut self) -> Result<f64, Error> {
+        self.get_fixed()
+    }
+
+    /// Parse a `String` pod, without copying it.
+    pub fn get_string(&mut self) -> Result<&'a str, Error> {
+        let (body, next) = self.next_pod(SpaType::String)?;
+        let string = match body.split_last() {
+            Some((0, string)) => std::str::from_utf8(string).map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        };
+        self.input = next;
+
+        Ok(string)
+    }
+
+    /// Parse a `Bytes` pod, without copying it.
+    pub fn get_bytes(&mut self) -> Result<&'a [u8], Error> {
+        let (body, next) = self.next_pod(SpaType::Bytes)?;
+        self.input = next;
+
+        Ok(body)
+    }
+
+    /// Parse a `Rectangle` pod.
+    pub fn get_rectangle(&mut self) -> Result<Rectangle, Error> {
+        self.get_fixed()
+    }
+
+    /// Parse a `Rectangle` pod.
+    ///
+    /// This is an alias of [`get_rectangle`](`Self::get_rectangle`).
+    pub fn get_rect(&mut self) -> Result<Rectangle, Error> {
+        self.get_rectangle()
+    }
+
+    /// Parse a `Fraction` pod.
+    pub fn get_fraction(&mut self) -> Result<Fraction, Error> {
+        self.get_fixed()
+    }
+
+    /// Parse a `Fd` pod.
+    pub fn get_fd(&mut self) -> Result<Fd, Error> {
+        self.get_fixed()
+    }
+
+    /// Parse an `Array` pod whose elements are of type `T`.
+    pub fn get_array<T: FixedSizedPod>(&mut self) -> Result<Vec<T>, Error> {
+        let mut parser = self.clone();
+        let elements = parser.enter_array()?.collect::<Result<_, _>>()?;
+        *self = parser;
+
+        Ok(elements)
+    }
+
+    /// Enter an `Array` pod whose elements are of type `T`, returning an iterator over its elements.
+    ///
+    /// The cursor of `self` moves past the whole array.
+    pub fn enter_array<T: FixedSizedPod>(&mut self) -> Result<ArrayParser<'a, T>, Error> {
+        let (body, next) = self.next_pod(SpaType::Array)?;
+
+        let child_size = read_u32(body, 0).ok_or_else(invalid)?;
+        let child_type = read_u32(body, 4).ok_or_else(invalid)?;
+        if child_type != T::CanonicalType::TYPE
+            || child_size != T::CanonicalType::SIZE
+            || child_size == 0
+        {
+            return Err(invalid());
+        }
+        self.input = next;
+
+        Ok(ArrayParser {
+            input: &body[HEADER_SIZE..],
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Enter a `Struct` pod, returning a parser over its fields.
+    ///
+    /// The cursor of `self` moves past the whole struct.
+    pub fn enter_struct(&mut self) -> Result<PodParser<'a>, Error> {
+        let (body, next) = self.next_pod(SpaType::Struct)?;
+        self.input = next;
+
+        Ok(PodParser::from_bytes(body))
+    }
+
+    /// Enter an `Object` pod, returning a parser over its properties.
+    ///
+    /// The cursor of `self` moves past the whole object.
+    pub fn enter_object(&mut self) -> Result<ObjectParser<'a>, Error> {
+        let (body, next) = self.next_pod(SpaType::Object)?;
+        let type_ = read_u32(body, 0).ok_or_else(invalid)?;
+        let id = read_u32(body, 4).ok_or_else(invalid)?;
+        self.input = next;
+
+        Ok(ObjectParser {
+            type_,
+            id,
+            input: &body[2 * mem::size_of::<u32>()..],
+        })
+    }
+}
+
+/// An iterator over the elements of an `Array` pod, obtained with [`PodParser::enter_array`].
+///
+/// Each element is returned as a `Result`, as it may not be valid for the type `T`.
+#[derive(Debug, Clone)]
+pub struct ArrayParser<'a, T: FixedSizedPod> {
+    input: &'a [u8],
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T: FixedSizedPod> ArrayParser<'a, T> {
+    /// The number of elements left to parse.
+    pub fn len(&self) -> usize {
+        self.input.len() / T::CanonicalType::SIZE as usize
+    }
+
+    /// Whether all elements have been parsed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a, T: FixedSizedPod> Iterator for ArrayParser<'a, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let size = T::CanonicalType::SIZE as usize;
+        if self.input.len() < size {
+            return None;
+        }
+
+        let (element, rest) = self.input.split_at(size);
+        self.input = rest;
+
+        Some(
+            T::CanonicalType::deserialize_body(element)
+                .map(|(_, value)| T::from_canonical_type(&value))
+                .map_err(|_| invalid()),
+        )
+    }
+}
+
+/// A cursor over the properties of an `Object` pod, obtained with [`PodParser::enter_object`].
+#[derive(Debug, Clone)]
+pub struct ObjectParser<'a> {
+    type_: u32,
+    id: u32,
+    input: &'a [u8],
+}
+
+impl<'a> ObjectParser<'a> {
+    /// The `SPA_TYPE_OBJECT_*` type of the object.
+    pub fn type_(&self) -> u32 {
+        self.type_
+    }
+
+    /// The id of the object, such as a `SPA_PARAM_*` id.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Parse the next property of the object.
+    ///
+    /// Returns the key and flags of the property, along with a parser whose only pod is the value,
+    /// or `None` if all properties have been parsed.
+    pub fn next_property(&mut self) -> Result<Option<(u32, PropertyFlags, PodParser<'a>)>, Error> {
+        if self.input.is_empty() {
+            return Ok(None);
+        }
+
+        let key = read_u32(self.input, 0).ok_or_else(invalid)?;
+        let flags = read_u32(self.input, 4).ok_or_else(invalid)?;
+        let rest = &self.input[2 * mem::size_of::<u32>()..];
+        let value = PodParser::from_bytes(rest);
+        let type_ = value.peek_type()?;
+        let type_ = SpaType::try_from(type_).map_err(|_| invalid())?;
+        let (_, next) = value.next_pod(type_)?;
+
+        let value = PodParser::from_bytes(&rest[..rest.len() - next.len()]);
+        self.input = next;
+
+        Ok(Some((key, PropertyFlags::from_bits_truncate(flags), value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PodParser;
+    use crate::pod::{builder::PodBuilder, PropertyFlags};
+    use crate::result::Error;
+    use crate::utils::{Id, Rectangle};
+
+    #[test]
+    fn struct_() {
+        let mut builder = PodBuilder::new();
+        let frame = builder.begin_struct();
+        builder.push_bool(true).unwrap();
+        builder.push_string("foo").unwrap();
+        builder
+            .push_rectangle(Rectangle {
+                width: 2,
+                height: 3,
+            })
+            .unwrap();
+        builder.end_struct(frame);
+        let pod = builder.build_owned();
+
+        let mut parser = PodParser::new(&pod);
+        let mut fields = parser.enter_struct().unwrap();
+        assert!(parser.is_empty());
+
+        assert_eq!(fields.get_bool(), Ok(true));
+        assert_eq!(fields.get_int(), Err(Error::new(libc::EINVAL)));
+        assert_eq!(fields.get_string(), Ok("foo"));
+        assert_eq!(
+            fields.get_rect(),
+            Ok(Rectangle {
+                width: 2,
+                height: 3
+            })
+        );
+        assert_eq!(fields.get_long(), Err(Error::new(libc::EPIPE)));
+    }
+
+    #[test]
+    fn object() {
+        let mut builder = PodBuilder::new();
+        let frame = builder.begin_object(
+            spa_sys::SPA_TYPE_OBJECT_Format,
+            spa_sys::SPA_PARAM_EnumFormat,
+        );
+        builder.push_property(spa_sys::SPA_FORMAT_mediaType, PropertyFlags::empty());
+        builder.push_id(Id(spa_sys::SPA_MEDIA_TYPE_audio)).unwrap();
+        builder.push_property(spa_sys::SPA_FORMAT_AUDIO_rate, PropertyFlags::HARDWARE);
+        builder.push_int(48000).unwrap();
+        builder.end_object(frame);
+        let pod = builder.build_owned();
+
+        let mut object = PodParser::new(&pod).enter_object().unwrap();
+        assert_eq!(object.type_(), spa_sys::SPA_TYPE_OBJECT_Format);
+        assert_eq!(object.id(), spa_sys::SPA_PARAM_EnumFormat);
+
+        let (key, flags, mut value) = object.next_property().unwrap().unwrap();
+        assert_eq!(key, spa_sys::SPA_FORMAT_mediaType);
+        assert_eq!(flags, PropertyFlags::empty());
+        assert_eq!(value.get_id(), Ok(Id(spa_sys::SPA_MEDIA_TYPE_audio)));
+
+        let (key, flags, mut value) = object.next_property().unwrap().unwrap();
+        assert_eq!(key, spa_sys::SPA_FORMAT_AUDIO_rate);
+        assert_eq!(flags, PropertyFlags::HARDWARE);
+        assert_eq!(value.get_int(), Ok(48000));
+
+        assert!(object.next_property().unwrap().is_none());
+    }
+
+    #[test]
+    fn array() {
+        let mut builder = PodBuilder::new();
+        let mut frame = builder.begin_array();
+        for i in 0..3i64 {
+            frame.push_element(i).unwrap();
+        }
+        frame.finish();
+        let pod = builder.build_owned();
+
+        assert_eq!(PodParser::new(&pod).get_array::<i64>(), Ok(vec![0, 1, 2]));
+        assert_eq!(
+            PodParser::new(&pod).get_array::<i32>(),
+            Err(Error::new(libc::EINVAL))
+        );
+
+        let mut parser = PodParser::new(&pod);
+        let mut elements = parser.enter_array::<i64>().unwrap();
+        assert!(parser.is_empty());
+        assert_eq!(elements.len(), 3);
+        assert_eq!(elements.next(), Some(Ok(0)));
+        assert_eq!(elements.collect::<Vec<_>>(), vec![Ok(1), Ok(2)]);
+    }
+}
@@ -0,0 +1,1075 @@
+//! This module provides a [`serde`](::serde) integration, allowing to convert any type implementing
+//! [`Serialize`](::serde::Serialize) and [`Deserialize`](::serde::Deserialize) from and to SPA pods.
+//!
+//! This module requires the `serde` feature.
+//!
+//! Types are first converted to a [`Value`], which is then serialized using the
+//! [`PodSerializer`](super::serialize::PodSerializer) of the [`serialize`](super::serialize) module,
+//! and the other way around for deserialization.
+//!
+//! Rust types are mapped to SPA pods like this:
+//! - `bool` is mapped to a `Bool` pod.
+//! - `i8`, `i16`, `i32`, `u8`, `u16` and `u32` are mapped to an `Int` pod,
+//!   `u32` values being stored with the same bits like in C.
+//! - `i64` and `u64` are mapped to a `Long` pod.
+//! - [`Id`] is mapped to an `Id` pod.
+//! - `f32` is mapped to a `Float` pod and `f64` to a `Double` pod.
+//! - `char` and strings are mapped to a `String` pod and byte arrays to a `Bytes` pod.
+//! - `()`, unit structs and `None` are mapped to a `None` pod.
+//! - `Some` and newtype structs are mapped to the pod of their content.
+//! - Sequences, tuples and tuple structs are mapped to a `Struct` pod.
+//! - Maps are mapped to a `Struct` pod containing alternating keys and values.
+//! - Structs are mapped to an `Object` pod.
+//!   If the name of the struct is of the form `"<type>:<id>"`, such as `"0x40003:3"`,
+//!   these are the type and id of the object, and are checked when deserializing. Otherwise they are both 0.
+//!   Fields whose name is a number, such as `"0x10003"`, use it as their property key,
+//!   other fields use their index in the struct.
+//!   Numbers can be written in decimal or in hexadecimal with a `0x` prefix,
+//!   and are usually set with `#[serde(rename = "...")]`.
+//! - Unit enum variants are mapped to an `Id` pod containing the variant index.
+//! - Other enum variants are mapped to a `Struct` pod containing an `Id` pod with the variant index,
+//!   followed by the pod of the variant content.
+//!
+//! # Examples
+//! ```rust
+//! use libspa::pod::serde::{from_bytes, to_bytes};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Settings {
+//!     rate: u32,
+//!     channels: i32,
+//!     name: String,
+//! }
+//!
+//! let settings = Settings {
+//!     rate: 48000,
+//!     channels: 2,
+//!     name: "stereo".to_string(),
+//! };
+//!
+//! let bytes = to_bytes(&settings).unwrap();
+//! let deserialized: Settings = from_bytes(&bytes).unwrap();
+//!
+//! assert_eq!(settings, deserialized);
+//! ```
+//!
+//! A `SPA_PARAM_EnumFormat` param for raw audio, using the `SPA_TYPE_OBJECT_Format` type
+//! and the `SPA_FORMAT_*` property keys:
+//! ```rust
+//! use libspa::pod::serde::to_value;
+//! use libspa::utils::Id;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! #[serde(rename = "0x40003:3")]
+//! struct AudioFormat {
+//!     #[serde(rename = "0x1")]
+//!     media_type: Id,
+//!     #[serde(rename = "0x2")]
+//!     media_subtype: Id,
+//!     #[serde(rename = "0x10003")]
+//!     rate: u32,
+//!     #[serde(rename = "0x10004")]
+//!     channels: u32,
+//! }
+//!
+//! let format = to_value(&AudioFormat {
+//!     media_type: Id(spa_sys::SPA_MEDIA_TYPE_audio),
+//!     media_subtype: Id(spa_sys::SPA_MEDIA_SUBTYPE_raw),
+//!     rate: 48000,
+//!     channels: 2,
+//! })
+//! .unwrap();
+//! ```
+
+use std::{convert::TryFrom, fmt, io::Cursor};
+
+use ::serde::{
+    de::{self, DeserializeOwned, IntoDeserializer, Visitor},
+    ser::{self, Serialize},
+};
+
+use super::{
+    deserialize::PodDeserializer as RawPodDeserializer,
+    serialize::PodSerializer as RawPodSerializer, Object, Property, PropertyFlags, Value,
+    ValueArray,
+};
+use crate::utils::Id;
+
+/// The name of the newtype struct [`Id`] is serialized as, so it can be told apart from a `u32`.
+const ID_NAME: &str = "$libspa::Id";
+
+impl Serialize for Id {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(ID_NAME, &self.0)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Id {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IdVisitor;
+
+        impl<'de> Visitor<'de> for IdVisitor {
+            type Value = Id;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an Id")
+            }
+
+            fn visit_newtype_struct<D: de::Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Id, D::Error> {
+                de::Deserialize::deserialize(deserializer).map(Id)
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(ID_NAME, IdVisitor)
+    }
+}
+
+/// Parse a decimal or `0x` prefixed hexadecimal number.
+fn parse_number(s: &str) -> Option<u32> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+/// Parse the object type and id from a struct name of the form `"<type>:<id>"`.
+fn parse_object_name(name: &str) -> Option<(u32, u32)> {
+    let mut parts = name.splitn(2, ':');
+    let type_ = parse_number(parts.next()?)?;
+    let id = parse_number(parts.next()?)?;
+    Some((type_, id))
+}
+
+/// Error raised when converting a type from or to a pod.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Convert a serializable type to a [`Value`].
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, Error> {
+    value.serialize(PodSerializer)
+}
+
+/// Serialize a serializable type to a raw pod.
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    let value = to_value(value)?;
+
+    RawPodSerializer::serialize(Cursor::new(Vec::new()), &value)
+        .map(|(out, _)| out.into_inner())
+        .map_err(|e| Error(format!("failed to serialize pod: {:?}", e)))
+}
+
+/// Convert a [`Value`] to a deserializable type.
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, Error> {
+    T::deserialize(PodDeserializer::new(value))
+}
+
+/// Deserialize a deserializable type from a raw pod.
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    let (_, value) = RawPodDeserializer::deserialize_from::<Value>(bytes)
+        .map_err(|e| Error(format!("failed to deserialize pod: {:?}", e)))?;
+
+    from_value(value)
+}
+
+/// A [`serde::Serializer`](::serde::Serializer) converting types to a [`Value`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PodSerializer;
+
+impl ser::Serializer for PodSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = SeqSerializer;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = StructSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+        Ok(Value::Int(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+        Ok(Value::Long(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        Ok(Value::Int(v as i32))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        i64::try_from(v)
+            .map(Value::Long)
+            .map_err(|_| Error(format!("{} does not fit in a Long pod", v)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+        Ok(Value::Float(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+        Ok(Value::Double(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        Ok(Value::Bytes(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        Ok(Value::None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Value::Id(Id(variant_index)))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        match value.serialize(self)? {
+            Value::Int(id) if name == ID_NAME => Ok(Value::Id(Id(id as u32))),
+            value => Ok(value),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        Ok(Value::Struct(vec![
+            Value::Id(Id(variant_index)),
+            value.serialize(self)?,
+        ]))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer::new(len.unwrap_or(0), None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer::new(len, None))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer::new(len, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer::new(len, Some(variant_index)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer::new(len.unwrap_or(0) * 2, None))
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<StructSerializer, Error> {
+        Ok(StructSerializer::new(name, len, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<StructSerializer, Error> {
+        Ok(StructSerializer::new(variant, len, Some(variant_index)))
+    }
+}
+
+/// Wrap `value` with the index of its enum variant, if any.
+fn wrap_variant(value: Value, variant_index: Option<u32>) -> Value {
+    match variant_index {
+        Some(index) => Value::Struct(vec![Value::Id(Id(index)), value]),
+        None => value,
+    }
+}
+
+/// Serializer for sequences, tuples and maps, producing a `Struct` pod.
+#[derive(Debug)]
+pub struct SeqSerializer {
+    values: Vec<Value>,
+    variant_index: Option<u32>,
+}
+
+impl SeqSerializer {
+    fn new(len: usize, variant_index: Option<u32>) -> Self {
+        Self {
+            values: Vec::with_capacity(len),
+            variant_index,
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.values.push(value.serialize(PodSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value, Error> {
+        Ok(wrap_variant(Value::Struct(self.values), self.variant_index))
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.push(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        self.finish()
+    }
+}
+
+/// Serializer for structs, producing an `Object` pod.
+#[derive(Debug)]
+pub struct StructSerializer {
+    type_: u32,
+    id: u32,
+    properties: Vec<Property>,
+    variant_index: Option<u32>,
+}
+
+impl StructSerializer {
+    fn new(name: &str, len: usize, variant_index: Option<u32>) -> Self {
+        let (type_, id) = parse_object_name(name).unwrap_or((0, 0));
+
+        Self {
+            type_,
+            id,
+            properties: Vec::with_capacity(len),
+            variant_index,
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, name: &str, value: &T) -> Result<(), Error> {
+        let key = match parse_number(name) {
+            Some(key) => key,
+            None => u32::try_from(self.properties.len()).expect("too many struct fields"),
+        };
+
+        self.properties.push(Property {
+            key,
+            flags: PropertyFlags::empty(),
+            value: value.serialize(PodSerializer)?,
+        });
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value, Error> {
+        let object = Value::Object(Object {
+            type_: self.type_,
+            id: self.id,
+            properties: self.properties,
+        });
+
+        Ok(wrap_variant(object, self.variant_index))
+    }
+}
+
+impl ser::SerializeStruct for StructSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for StructSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        self.finish()
+    }
+}
+
+/// A [`serde::Deserializer`](::serde::Deserializer) converting a [`Value`] to other types.
+#[derive(Debug)]
+pub struct PodDeserializer {
+    value: Value,
+}
+
+impl PodDeserializer {
+    /// Create a deserializer reading from `value`.
+    pub fn new(value: Value) -> Self {
+        Self { value }
+    }
+}
+
+fn array_values(array: ValueArray) -> Vec<Value> {
+    match array {
+        ValueArray::None(v) => v.into_iter().map(|_| Value::None).collect(),
+        ValueArray::Bool(v) => v.into_iter().map(Value::Bool).collect(),
+        ValueArray::Id(v) => v.into_iter().map(Value::Id).collect(),
+        ValueArray::Int(v) => v.into_iter().map(Value::Int).collect(),
+        ValueArray::Long(v) => v.into_iter().map(Value::Long).collect(),
+        ValueArray::Float(v) => v.into_iter().map(Value::Float).collect(),
+        ValueArray::Double(v) => v.into_iter().map(Value::Double).collect(),
+        ValueArray::Rectangle(v) => v.into_iter().map(Value::Rectangle).collect(),
+        ValueArray::Fraction(v) => v.into_iter().map(Value::Fraction).collect(),
+        ValueArray::Fd(v) => v.into_iter().map(Value::Fd).collect(),
+    }
+}
+
+impl<'de> de::Deserializer<'de> for PodDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::None => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::Id(Id(v)) => visitor.visit_u32(v),
+            Value::Int(v) => visitor.visit_i32(v),
+            Value::Long(v) => visitor.visit_i64(v),
+            Value::Float(v) => visitor.visit_f32(v),
+            Value::Double(v) => visitor.visit_f64(v),
+            Value::String(v) => visitor.visit_string(v),
+            Value::Bytes(v) => visitor.visit_byte_buf(v),
+            Value::Rectangle(v) => visitor.visit_seq(SeqAccess::new(vec![
+                Value::Id(Id(v.width)),
+                Value::Id(Id(v.height)),
+            ])),
+            Value::Fraction(v) => visitor.visit_seq(SeqAccess::new(vec![
+                Value::Id(Id(v.num)),
+                Value::Id(Id(v.denom)),
+            ])),
+            Value::ValueArray(v) => visitor.visit_seq(SeqAccess::new(array_values(v))),
+            Value::Struct(v) => visitor.visit_seq(SeqAccess::new(v)),
+            Value::Object(v) => visitor.visit_map(ObjectAccess::new(v.properties, None)),
+            Value::Fd(_) | Value::Choice(_) | Value::Pointer(_, _) => Err(Error(
+                "Fd, Choice and Pointer pods cannot be deserialized".to_string(),
+            )),
+        }
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Int(v) => visitor.visit_u32(v as u32),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::None => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Id(Id(id)) if name == ID_NAME => {
+                visitor.visit_newtype_struct(id.into_deserializer())
+            }
+            _ if name == ID_NAME => Err(Error("expected an Id pod".to_string())),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Object(object) => {
+                if let Some((type_, id)) = parse_object_name(name) {
+                    if object.type_ != type_ || object.id != id {
+                        return Err(Error(format!(
+                            "expected an Object pod of type {} and id {}, got type {} and id {}",
+                            type_, id, object.type_, object.id
+                        )));
+                    }
+                }
+
+                visitor.visit_map(ObjectAccess::new(object.properties, Some(fields)))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Struct(v) if v.len() % 2 == 0 => visitor.visit_map(MapAccess::new(v)),
+            Value::Object(v) => visitor.visit_map(ObjectAccess::new(v.properties, None)),
+            _ => Err(Error(
+                "expected a Struct pod with keys and values".to_string(),
+            )),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Id(Id(index)) => visitor.visit_enum(EnumAccess {
+                index,
+                content: None,
+            }),
+            Value::Struct(mut v) if v.len() == 2 => {
+                let content = v.pop();
+                match v.pop() {
+                    Some(Value::Id(Id(index))) => visitor.visit_enum(EnumAccess { index, content }),
+                    _ => Err(Error("expected an Id pod as enum variant".to_string())),
+                }
+            }
+            _ => Err(Error("expected an Id or Struct pod as enum".to_string())),
+        }
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct identifier ignored_any
+    }
+}
+
+/// Access the content of a `Struct` pod as a sequence.
+struct SeqAccess {
+    values: std::vec::IntoIter<Value>,
+}
+
+impl SeqAccess {
+    fn new(values: Vec<Value>) -> Self {
+        Self {
+            values: values.into_iter(),
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.values
+            .next()
+            .map(|value| seed.deserialize(PodDeserializer::new(value)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// Access the content of a `Struct` pod with alternating keys and values as a map.
+struct MapAccess {
+    values: std::vec::IntoIter<Value>,
+}
+
+impl MapAccess {
+    fn new(values: Vec<Value>) -> Self {
+        Self {
+            values: values.into_iter(),
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        self.values
+            .next()
+            .map(|key| seed.deserialize(PodDeserializer::new(key)))
+            .transpose()
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .values
+            .next()
+            .ok_or_else(|| Error("missing map value".to_string()))?;
+        seed.deserialize(PodDeserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len() / 2)
+    }
+}
+
+/// Access the properties of an `Object` pod as a map, using the property keys as map keys.
+///
+/// When deserializing a struct, keys are instead mapped to the name of the field with that key,
+/// or used as the index of the field if no field has a number as name.
+struct ObjectAccess {
+    properties: std::vec::IntoIter<Property>,
+    fields: Option<&'static [&'static str]>,
+    value: Option<Value>,
+}
+
+impl ObjectAccess {
+    fn new(properties: Vec<Property>, fields: Option<&'static [&'static str]>) -> Self {
+        Self {
+            properties: properties.into_iter(),
+            fields,
+            value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for ObjectAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.properties.next() {
+            Some(property) => {
+                self.value = Some(property.value);
+                let key = property.key;
+
+                match self.fields {
+                    Some(fields) if fields.iter().any(|f| parse_number(f).is_some()) => {
+                        match fields.iter().find(|f| parse_number(f) == Some(key)) {
+                            Some(field) => seed.deserialize((*field).into_deserializer()).map(Some),
+                            // Unknown property, not matching any field name
+                            None => seed
+                                .deserialize(key.to_string().into_deserializer())
+                                .map(Some),
+                        }
+                    }
+                    _ => seed.deserialize(key.into_deserializer()).map(Some),
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error("missing property value".to_string()))?;
+        seed.deserialize(PodDeserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.properties.len())
+    }
+}
+
+/// Access an enum variant, made of its index and optional content.
+struct EnumAccess {
+    index: u32,
+    content: Option<Value>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess), Error> {
+        let index: de::value::U32Deserializer<Error> = self.index.into_deserializer();
+        let variant = seed.deserialize(index)?;
+
+        Ok((
+            variant,
+            VariantAccess {
+                content: self.content,
+            },
+        ))
+    }
+}
+
+struct VariantAccess {
+    content: Option<Value>,
+}
+
+impl VariantAccess {
+    fn content(self) -> Result<PodDeserializer, Error> {
+        self.content
+            .map(PodDeserializer::new)
+            .ok_or_else(|| Error("missing enum variant content".to_string()))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.content {
+            None => Ok(()),
+            Some(_) => Err(Error("unexpected enum variant content".to_string())),
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.content()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self.content()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_struct(self.content()?, "", fields, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ::serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Mode {
+        Off,
+        Fixed(u32),
+        Range(i32, i32),
+        Custom { name: String, gain: f64 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        enabled: bool,
+        rate: u32,
+        volume: f32,
+        label: Option<String>,
+        channels: Vec<i64>,
+        modes: Vec<Mode>,
+        extra: BTreeMap<String, i32>,
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(to_value(&true).unwrap(), Value::Bool(true));
+        assert_eq!(to_value(&42i32).unwrap(), Value::Int(42));
+        assert_eq!(to_value(&42u32).unwrap(), Value::Int(42));
+        assert_eq!(to_value(&Id(3)).unwrap(), Value::Id(Id(3)));
+        assert_eq!(from_value::<u32>(Value::Int(-1)).unwrap(), u32::MAX);
+        assert_eq!(from_value::<Id>(Value::Id(Id(3))).unwrap(), Id(3));
+        assert!(from_value::<Id>(Value::Int(3)).is_err());
+        assert_eq!(to_value(&1.5f64).unwrap(), Value::Double(1.5));
+        assert_eq!(to_value("foo").unwrap(), Value::String("foo".to_string()));
+        assert_eq!(to_value(&()).unwrap(), Value::None);
+        assert!(to_value(&u64::MAX).is_err());
+    }
+
+    #[test]
+    fn structs() {
+        #[derive(Serialize)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        assert_eq!(
+            to_value(&Point { x: 1, y: 2 }).unwrap(),
+            Value::Object(Object {
+                type_: 0,
+                id: 0,
+                properties: vec![
+                    Property {
+                        key: 0,
+                        flags: PropertyFlags::empty(),
+                        value: Value::Int(1),
+                    },
+                    Property {
+                        key: 1,
+                        flags: PropertyFlags::empty(),
+                        value: Value::Int(2),
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn keyed_structs() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        #[serde(rename = "0x40003:3")]
+        struct AudioFormat {
+            #[serde(rename = "0x1")]
+            media_type: Id,
+            #[serde(rename = "0x10003")]
+            rate: u32,
+            #[serde(rename = "65540")]
+            channels: u32,
+        }
+
+        let format = AudioFormat {
+            media_type: Id(spa_sys::SPA_MEDIA_TYPE_audio),
+            rate: 48000,
+            channels: 2,
+        };
+
+        let value = to_value(&format).unwrap();
+        assert_eq!(
+            value,
+            Value::Object(Object {
+                type_: spa_sys::SPA_TYPE_OBJECT_Format,
+                id: spa_sys::SPA_PARAM_EnumFormat,
+                properties: vec![
+                    Property {
+                        key: spa_sys::SPA_FORMAT_mediaType,
+                        flags: PropertyFlags::empty(),
+                        value: Value::Id(Id(spa_sys::SPA_MEDIA_TYPE_audio)),
+                    },
+                    Property {
+                        key: spa_sys::SPA_FORMAT_AUDIO_rate,
+                        flags: PropertyFlags::empty(),
+                        value: Value::Int(48000),
+                    },
+                    Property {
+                        key: spa_sys::SPA_FORMAT_AUDIO_channels,
+                        flags: PropertyFlags::empty(),
+                        value: Value::Int(2),
+                    },
+                ],
+            })
+        );
+
+        // Properties can be in any order, and unknown ones are ignored.
+        let mut object = match value {
+            Value::Object(object) => object,
+            _ => unreachable!(),
+        };
+        object.properties.reverse();
+        object.properties.push(Property {
+            key: spa_sys::SPA_FORMAT_AUDIO_format,
+            flags: PropertyFlags::empty(),
+            value: Value::Id(Id(spa_sys::SPA_AUDIO_FORMAT_S16_LE)),
+        });
+        assert_eq!(
+            from_value::<AudioFormat>(Value::Object(object.clone())).unwrap(),
+            format
+        );
+
+        object.id = spa_sys::SPA_PARAM_Format;
+        assert!(from_value::<AudioFormat>(Value::Object(object)).is_err());
+    }
+
+    #[test]
+    fn enums() {
+        assert_eq!(to_value(&Mode::Off).unwrap(), Value::Id(Id(0)));
+        assert_eq!(
+            to_value(&Mode::Fixed(3)).unwrap(),
+            Value::Struct(vec![Value::Id(Id(1)), Value::Int(3)])
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut extra = BTreeMap::new();
+        extra.insert("a".to_string(), 1);
+        extra.insert("b".to_string(), 2);
+
+        let settings = Settings {
+            enabled: true,
+            rate: 48000,
+            volume: 0.5,
+            label: None,
+            channels: vec![1, 2, 3],
+            modes: vec![
+                Mode::Off,
+                Mode::Fixed(2),
+                Mode::Range(-1, 1),
+                Mode::Custom {
+                    name: "custom".to_string(),
+                    gain: 1.5,
+                },
+            ],
+            extra,
+        };
+
+        let bytes = to_bytes(&settings).unwrap();
+        let deserialized: Settings = from_bytes(&bytes).unwrap();
+        assert_eq!(settings, deserialized);
+
+        let labelled = Settings {
+            label: Some("label".to_string()),
+            ..deserialized
+        };
+        let value = to_value(&labelled).unwrap();
+        assert_eq!(from_value::<Settings>(value).unwrap(), labelled);
+    }
+}
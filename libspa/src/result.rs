@@ -123,6 +123,18 @@ impl SpaResult {
             SpaSuccess::Async(_) => panic!("result is an asynchronous success"),
         }
     }
+
+    /// Convert a [`SpaResult`] into a [`std::io::Result`].
+    ///
+    /// Errors are mapped to the [`std::io::Error`] of their errno value.
+    /// Successes, including asynchronous ones, are returned as their raw value.
+    pub fn to_io_error(self) -> std::io::Result<i32> {
+        if self.0 < 0 {
+            Err(std::io::Error::from_raw_os_error(-self.0))
+        } else {
+            Ok(self.0)
+        }
+    }
 }
 
 /// Error returned from a SPA method.
@@ -130,15 +142,49 @@ impl SpaResult {
 pub struct Error(Errno);
 
 impl Error {
-    fn new(e: i32) -> Self {
+    pub(crate) fn new(e: i32) -> Self {
         assert!(e > 0);
 
         Self(Errno(e))
     }
 }
 
+impl Error {
+    /// Convert the error to the [`std::io::Error`] of its errno value.
+    pub fn to_io_error(&self) -> std::io::Error {
+        std::io::Error::from_raw_os_error((self.0).0)
+    }
+}
+
 impl std::error::Error for Error {}
 
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        err.to_io_error()
+    }
+}
+
+impl From<std::io::Error> for Error {
+    /// I/O errors which are not OS errors are converted to `EIO`.
+    fn from(err: std::io::Error) -> Self {
+        match err.raw_os_error() {
+            Some(e) if e > 0 => Self::new(e),
+            _ => Self::new(libc::EIO),
+        }
+    }
+}
+
+impl From<cookie_factory::GenError> for Error {
+    /// Running out of space in the buffer is converted to `ENOSPC`, other errors to `EINVAL`.
+    fn from(err: cookie_factory::GenError) -> Self {
+        match err {
+            cookie_factory::GenError::BufferTooSmall(_)
+            | cookie_factory::GenError::BufferTooBig(_) => Self::new(libc::ENOSPC),
+            _ => Self::new(libc::EINVAL),
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -176,6 +222,29 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn io_error() {
+        let err = SpaResult::from_c(-libc::EBUSY).into_result().unwrap_err();
+        let io_err: std::io::Error = err.into();
+        assert_eq!(io_err.raw_os_error(), Some(libc::EBUSY));
+
+        let err: Error = io_err.into();
+        assert_eq!(err, Error::new(libc::EBUSY));
+
+        let err: Error = std::io::Error::new(std::io::ErrorKind::Other, "oops").into();
+        assert_eq!(err, Error::new(libc::EIO));
+    }
+
+    #[test]
+    fn to_io_error() {
+        assert_eq!(SpaResult::from_c(0).to_io_error().unwrap(), 0);
+        assert_eq!(SpaResult::from_c(3).to_io_error().unwrap(), 3);
+
+        let err = SpaResult::from_c(-libc::ENOENT).to_io_error().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENOENT));
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    }
+
     #[test]
     fn async_seq() {
         assert_eq!(AsyncSeq::from_seq(0).seq(), 0);
@@ -0,0 +1,160 @@
+// Copyright The pipewire-rs Contributors.
+// SPDX-License-Identifier: MIT
+
+//! SPA ring buffer
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// A lock-free single-producer / single-consumer ring buffer, compatible with the C `spa_ringbuffer`.
+///
+/// Like `spa_ringbuffer`, it only keeps track of the read and write indexes.
+/// The data is stored in a separate buffer whose size must be a power of two,
+/// which is passed to [`read_data`](Self::read_data) and [`write_data`](Self::write_data).
+///
+/// The indexes are atomics, so the ring buffer is `Send` and `Sync` and can be shared between
+/// the producer and the consumer threads.
+/// There must only ever be a single producer, calling the `write` methods,
+/// and a single consumer, calling the `read` methods. This is not required for memory safety,
+/// but the indexes may get out of sync otherwise.
+#[derive(Debug, Default)]
+#[repr(C)]
+pub struct RingBuffer {
+    readindex: AtomicU32,
+    writeindex: AtomicU32,
+}
+
+impl RingBuffer {
+    /// Create a new, empty ring buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a raw pointer to the ring buffer, as a `spa_ringbuffer`.
+    pub fn as_raw_ptr(&self) -> *mut spa_sys::spa_ringbuffer {
+        self as *const _ as *mut _
+    }
+
+    /// Get the read index and the number of bytes available for reading.
+    ///
+    /// A negative or too large value means that the buffer was underrun or overrun.
+    pub fn get_read_index(&self) -> (u32, i32) {
+        let index = self.readindex.load(Ordering::Relaxed);
+        let filled = self.writeindex.load(Ordering::Acquire).wrapping_sub(index) as i32;
+        (index, filled)
+    }
+
+    /// Read `data.len()` bytes at `index` from `buffer`, wrapping around at the end of `buffer`.
+    ///
+    /// # Panics
+    /// If the size of `buffer` is not a power of two, or if `data` is larger than `buffer`.
+    pub fn read_data(&self, buffer: &[u8], index: u32, data: &mut [u8]) {
+        let offset = Self::offset(buffer.len(), index, data.len());
+        let first = data.len().min(buffer.len() - offset);
+
+        data[..first].copy_from_slice(&buffer[offset..offset + first]);
+        let rest = data.len() - first;
+        data[first..].copy_from_slice(&buffer[..rest]);
+    }
+
+    /// Update the read index after reading data, making the space available for writing.
+    pub fn read_update(&self, index: u32) {
+        self.readindex.store(index, Ordering::Release);
+    }
+
+    /// Get the write index and the number of bytes that are filled and not read yet.
+    ///
+    /// A negative or too large value means that the buffer was underrun or overrun.
+    pub fn get_write_index(&self) -> (u32, i32) {
+        let index = self.writeindex.load(Ordering::Relaxed);
+        let filled = index.wrapping_sub(self.readindex.load(Ordering::Acquire)) as i32;
+        (index, filled)
+    }
+
+    /// Write `data` at `index` into `buffer`, wrapping around at the end of `buffer`.
+    ///
+    /// # Panics
+    /// If the size of `buffer` is not a power of two, or if `data` is larger than `buffer`.
+    pub fn write_data(&self, buffer: &mut [u8], index: u32, data: &[u8]) {
+        let offset = Self::offset(buffer.len(), index, data.len());
+        let first = data.len().min(buffer.len() - offset);
+
+        buffer[offset..offset + first].copy_from_slice(&data[..first]);
+        let rest = data.len() - first;
+        buffer[..rest].copy_from_slice(&data[first..]);
+    }
+
+    /// Update the write index after writing data, making the data available for reading.
+    pub fn write_update(&self, index: u32) {
+        self.writeindex.store(index, Ordering::Release);
+    }
+
+    /// Get the offset of `index` in a buffer of size `size`.
+    fn offset(size: usize, index: u32, len: usize) -> usize {
+        assert!(
+            size.is_power_of_two(),
+            "Buffer size {} is not a power of two",
+            size
+        );
+        assert!(
+            len <= size,
+            "Data of {} bytes does not fit in a buffer of {} bytes",
+            len,
+            size
+        );
+
+        index as usize & (size - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        assert_eq!(
+            std::mem::size_of::<RingBuffer>(),
+            std::mem::size_of::<spa_sys::spa_ringbuffer>()
+        );
+    }
+
+    #[test]
+    fn read_write() {
+        let ring = RingBuffer::new();
+        let mut buffer = [0u8; 8];
+
+        let (index, filled) = ring.get_write_index();
+        assert_eq!((index, filled), (0, 0));
+        ring.write_data(&mut buffer, index, b"abcdef");
+        ring.write_update(index + 6);
+
+        let (index, filled) = ring.get_read_index();
+        assert_eq!((index, filled), (0, 6));
+        let mut data = [0u8; 4];
+        ring.read_data(&buffer, index, &mut data);
+        ring.read_update(index + 4);
+        assert_eq!(&data, b"abcd");
+
+        // Wrap around the end of the buffer
+        let (index, filled) = ring.get_write_index();
+        assert_eq!((index, filled), (6, 2));
+        ring.write_data(&mut buffer, index, b"ghij");
+        ring.write_update(index + 4);
+        assert_eq!(&buffer, b"ijcdefgh");
+
+        let (index, filled) = ring.get_read_index();
+        assert_eq!((index, filled), (4, 6));
+        let mut data = [0u8; 6];
+        ring.read_data(&buffer, index, &mut data);
+        ring.read_update(index + 6);
+        assert_eq!(&data, b"efghij");
+
+        assert_eq!(ring.get_read_index(), (10, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_power_of_two() {
+        RingBuffer::new().write_data(&mut [0u8; 6], 0, b"a");
+    }
+}
@@ -1,12 +1,263 @@
 //! Miscellaneous and utility items.
 
-use bitflags::bitflags;
+use std::{convert::TryFrom, fmt};
 
-pub use spa_sys::spa_fraction as Fraction;
-pub use spa_sys::spa_rectangle as Rectangle;
+use bitflags::bitflags;
 
 use crate::pod::CanonicalFixedSizedPod;
 
+/// The type of a pod, as found in its header,
+/// or the type of an `Object` pod, such as [`SpaType::ObjectFormat`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum SpaType {
+    None,
+    Bool,
+    Id,
+    Int,
+    Long,
+    Float,
+    Double,
+    String,
+    Bytes,
+    Rectangle,
+    Fraction,
+    Bitmap,
+    Array,
+    Struct,
+    Object,
+    Sequence,
+    Pointer,
+    Fd,
+    Choice,
+    Pod,
+    ObjectPropInfo,
+    ObjectProps,
+    ObjectFormat,
+    ObjectParamBuffers,
+    ObjectParamMeta,
+    ObjectParamIo,
+    ObjectParamProfile,
+    ObjectParamPortConfig,
+    ObjectParamRoute,
+    ObjectProfiler,
+    ObjectParamLatency,
+    ObjectParamProcessLatency,
+    EventDevice,
+    EventNode,
+    CommandDevice,
+    CommandNode,
+}
+
+impl SpaType {
+    /// The raw `SPA_TYPE_*` value of the type.
+    pub fn as_raw(&self) -> u32 {
+        match self {
+            Self::None => spa_sys::SPA_TYPE_None,
+            Self::Bool => spa_sys::SPA_TYPE_Bool,
+            Self::Id => spa_sys::SPA_TYPE_Id,
+            Self::Int => spa_sys::SPA_TYPE_Int,
+            Self::Long => spa_sys::SPA_TYPE_Long,
+            Self::Float => spa_sys::SPA_TYPE_Float,
+            Self::Double => spa_sys::SPA_TYPE_Double,
+            Self::String => spa_sys::SPA_TYPE_String,
+            Self::Bytes => spa_sys::SPA_TYPE_Bytes,
+            Self::Rectangle => spa_sys::SPA_TYPE_Rectangle,
+            Self::Fraction => spa_sys::SPA_TYPE_Fraction,
+            Self::Bitmap => spa_sys::SPA_TYPE_Bitmap,
+            Self::Array => spa_sys::SPA_TYPE_Array,
+            Self::Struct => spa_sys::SPA_TYPE_Struct,
+            Self::Object => spa_sys::SPA_TYPE_Object,
+            Self::Sequence => spa_sys::SPA_TYPE_Sequence,
+            Self::Pointer => spa_sys::SPA_TYPE_Pointer,
+            Self::Fd => spa_sys::SPA_TYPE_Fd,
+            Self::Choice => spa_sys::SPA_TYPE_Choice,
+            Self::Pod => spa_sys::SPA_TYPE_Pod,
+            Self::ObjectPropInfo => spa_sys::SPA_TYPE_OBJECT_PropInfo,
+            Self::ObjectProps => spa_sys::SPA_TYPE_OBJECT_Props,
+            Self::ObjectFormat => spa_sys::SPA_TYPE_OBJECT_Format,
+            Self::ObjectParamBuffers => spa_sys::SPA_TYPE_OBJECT_ParamBuffers,
+            Self::ObjectParamMeta => spa_sys::SPA_TYPE_OBJECT_ParamMeta,
+            Self::ObjectParamIo => spa_sys::SPA_TYPE_OBJECT_ParamIO,
+            Self::ObjectParamProfile => spa_sys::SPA_TYPE_OBJECT_ParamProfile,
+            Self::ObjectParamPortConfig => spa_sys::SPA_TYPE_OBJECT_ParamPortConfig,
+            Self::ObjectParamRoute => spa_sys::SPA_TYPE_OBJECT_ParamRoute,
+            Self::ObjectProfiler => spa_sys::SPA_TYPE_OBJECT_Profiler,
+            Self::ObjectParamLatency => spa_sys::SPA_TYPE_OBJECT_ParamLatency,
+            Self::ObjectParamProcessLatency => spa_sys::SPA_TYPE_OBJECT_ParamProcessLatency,
+            Self::EventDevice => spa_sys::SPA_TYPE_EVENT_Device,
+            Self::EventNode => spa_sys::SPA_TYPE_EVENT_Node,
+            Self::CommandDevice => spa_sys::SPA_TYPE_COMMAND_Device,
+            Self::CommandNode => spa_sys::SPA_TYPE_COMMAND_Node,
+        }
+    }
+}
+
+impl TryFrom<u32> for SpaType {
+    type Error = u32;
+
+    /// Convert a raw `SPA_TYPE_*` value, returning it back as error if it is not a pod or object type.
+    fn try_from(raw: u32) -> Result<Self, Self::Error> {
+        match raw {
+            spa_sys::SPA_TYPE_None => Ok(Self::None),
+            spa_sys::SPA_TYPE_Bool => Ok(Self::Bool),
+            spa_sys::SPA_TYPE_Id => Ok(Self::Id),
+            spa_sys::SPA_TYPE_Int => Ok(Self::Int),
+            spa_sys::SPA_TYPE_Long => Ok(Self::Long),
+            spa_sys::SPA_TYPE_Float => Ok(Self::Float),
+            spa_sys::SPA_TYPE_Double => Ok(Self::Double),
+            spa_sys::SPA_TYPE_String => Ok(Self::String),
+            spa_sys::SPA_TYPE_Bytes => Ok(Self::Bytes),
+            spa_sys::SPA_TYPE_Rectangle => Ok(Self::Rectangle),
+            spa_sys::SPA_TYPE_Fraction => Ok(Self::Fraction),
+            spa_sys::SPA_TYPE_Bitmap => Ok(Self::Bitmap),
+            spa_sys::SPA_TYPE_Array => Ok(Self::Array),
+            spa_sys::SPA_TYPE_Struct => Ok(Self::Struct),
+            spa_sys::SPA_TYPE_Object => Ok(Self::Object),
+            spa_sys::SPA_TYPE_Sequence => Ok(Self::Sequence),
+            spa_sys::SPA_TYPE_Pointer => Ok(Self::Pointer),
+            spa_sys::SPA_TYPE_Fd => Ok(Self::Fd),
+            spa_sys::SPA_TYPE_Choice => Ok(Self::Choice),
+            spa_sys::SPA_TYPE_Pod => Ok(Self::Pod),
+            spa_sys::SPA_TYPE_OBJECT_PropInfo => Ok(Self::ObjectPropInfo),
+            spa_sys::SPA_TYPE_OBJECT_Props => Ok(Self::ObjectProps),
+            spa_sys::SPA_TYPE_OBJECT_Format => Ok(Self::ObjectFormat),
+            spa_sys::SPA_TYPE_OBJECT_ParamBuffers => Ok(Self::ObjectParamBuffers),
+            spa_sys::SPA_TYPE_OBJECT_ParamMeta => Ok(Self::ObjectParamMeta),
+            spa_sys::SPA_TYPE_OBJECT_ParamIO => Ok(Self::ObjectParamIo),
+            spa_sys::SPA_TYPE_OBJECT_ParamProfile => Ok(Self::ObjectParamProfile),
+            spa_sys::SPA_TYPE_OBJECT_ParamPortConfig => Ok(Self::ObjectParamPortConfig),
+            spa_sys::SPA_TYPE_OBJECT_ParamRoute => Ok(Self::ObjectParamRoute),
+            spa_sys::SPA_TYPE_OBJECT_Profiler => Ok(Self::ObjectProfiler),
+            spa_sys::SPA_TYPE_OBJECT_ParamLatency => Ok(Self::ObjectParamLatency),
+            spa_sys::SPA_TYPE_OBJECT_ParamProcessLatency => Ok(Self::ObjectParamProcessLatency),
+            spa_sys::SPA_TYPE_EVENT_Device => Ok(Self::EventDevice),
+            spa_sys::SPA_TYPE_EVENT_Node => Ok(Self::EventNode),
+            spa_sys::SPA_TYPE_COMMAND_Device => Ok(Self::CommandDevice),
+            spa_sys::SPA_TYPE_COMMAND_Node => Ok(Self::CommandNode),
+            _ => Err(raw),
+        }
+    }
+}
+
+impl fmt::Display for SpaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A rectangle, such as the size of a video frame.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Rectangle {
+    /// The width of the rectangle.
+    pub width: u32,
+    /// The height of the rectangle.
+    pub height: u32,
+}
+
+impl Rectangle {
+    /// The area of the rectangle, `width * height`.
+    pub fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+}
+
+impl From<spa_sys::spa_rectangle> for Rectangle {
+    fn from(rect: spa_sys::spa_rectangle) -> Self {
+        Self {
+            width: rect.width,
+            height: rect.height,
+        }
+    }
+}
+
+impl From<Rectangle> for spa_sys::spa_rectangle {
+    fn from(rect: Rectangle) -> Self {
+        Self {
+            width: rect.width,
+            height: rect.height,
+        }
+    }
+}
+
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// A fraction, such as the frame rate of a video.
+///
+/// Fractions are compared field by field, so `1/2` and `2/4` are not equal.
+/// Use [`Fraction::simplify`] to compare their values.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Fraction {
+    /// The numerator.
+    pub num: u32,
+    /// The denominator.
+    pub denom: u32,
+}
+
+impl Fraction {
+    /// The value of the fraction as a float.
+    ///
+    /// Returns infinity or NaN if the denominator is zero.
+    pub fn as_f64(&self) -> f64 {
+        f64::from(self.num) / f64::from(self.denom)
+    }
+
+    /// Reduce the fraction to its lowest terms, such as `30/1` for `60/2`.
+    ///
+    /// Fractions with a zero denominator are returned unchanged.
+    #[must_use]
+    pub fn simplify(&self) -> Self {
+        fn gcd(mut a: u32, mut b: u32) -> u32 {
+            while b != 0 {
+                let r = a % b;
+                a = b;
+                b = r;
+            }
+            a
+        }
+
+        if self.denom == 0 {
+            return *self;
+        }
+
+        let gcd = gcd(self.num, self.denom);
+        Self {
+            num: self.num / gcd,
+            denom: self.denom / gcd,
+        }
+    }
+}
+
+impl From<spa_sys::spa_fraction> for Fraction {
+    fn from(fraction: spa_sys::spa_fraction) -> Self {
+        Self {
+            num: fraction.num,
+            denom: fraction.denom,
+        }
+    }
+}
+
+impl From<Fraction> for spa_sys::spa_fraction {
+    fn from(fraction: Fraction) -> Self {
+        Self {
+            num: fraction.num,
+            denom: fraction.denom,
+        }
+    }
+}
+
+impl fmt::Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.num, self.denom)
+    }
+}
+
 /// An enumerated value in a pod
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Id(pub u32);
@@ -69,3 +320,39 @@ pub enum ChoiceEnum<T: CanonicalFixedSizedPod> {
         flags: Vec<T>,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangle() {
+        let rect = Rectangle {
+            width: 1920,
+            height: 1080,
+        };
+        assert_eq!(rect.area(), 2_073_600);
+        assert_eq!(rect.to_string(), "1920x1080");
+
+        let raw: spa_sys::spa_rectangle = rect.into();
+        assert_eq!(Rectangle::from(raw), rect);
+    }
+
+    #[test]
+    fn fraction() {
+        let fraction = Fraction { num: 60, denom: 2 };
+        assert_eq!(fraction.as_f64(), 30.0);
+        assert_eq!(fraction.simplify(), Fraction { num: 30, denom: 1 });
+        assert_eq!(fraction.to_string(), "60/2");
+
+        let zero = Fraction { num: 0, denom: 0 };
+        assert_eq!(zero.simplify(), zero);
+        assert_eq!(
+            Fraction { num: 0, denom: 5 }.simplify(),
+            Fraction { num: 0, denom: 1 }
+        );
+
+        let raw: spa_sys::spa_fraction = fraction.into();
+        assert_eq!(Fraction::from(raw), fraction);
+    }
+}
@@ -10,8 +10,18 @@ fn main() {
         .expect("Cannot find libpipewire");
     let libpipewire = libs.get_by_name("libpipewire").unwrap();
 
-    // Tell cargo to invalidate the built crate whenever the wrapper changes
+    // Tell cargo to invalidate the built crate whenever the wrapper or the log shim change
     println!("cargo:rerun-if-changed=wrapper.h");
+    println!("cargo:rerun-if-changed=log.h");
+    println!("cargo:rerun-if-changed=log.c");
+
+    // The log shim implements the variadic `spa_log` methods, which cannot be written in Rust.
+    libpipewire
+        .include_paths
+        .iter()
+        .fold(&mut cc::Build::new(), |build, l| build.include(l))
+        .file("log.c")
+        .compile("pipewire-rs-log");
 
     let builder = bindgen::Builder::default()
         .header("wrapper.h")
@@ -20,7 +30,9 @@ fn main() {
         .parse_callbacks(Box::new(bindgen::CargoCallbacks))
         .size_t_is_usize(true)
         .allowlist_function("pw_.*")
+        .allowlist_function("pipewire_rs_.*")
         .allowlist_type("pw_.*")
+        .allowlist_type("pipewire_rs_.*")
         .allowlist_var("pw_.*")
         .allowlist_var("PW_.*")
         .blocklist_function("spa_.*")
@@ -0,0 +1,92 @@
+/* Copyright The pipewire-rs Contributors.
+ * SPDX-License-Identifier: MIT
+ */
+
+#include <stdarg.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "log.h"
+
+/* The size of the buffer messages are formatted into, longer messages are truncated. */
+#define MESSAGE_SIZE 4096
+
+struct logger {
+	struct spa_log log;
+	pipewire_rs_log_handler handler;
+	void *data;
+};
+
+static void impl_logtv(void *object, enum spa_log_level level, const struct spa_log_topic *topic,
+		       const char *file, int line, const char *func, const char *fmt, va_list args)
+{
+	struct logger *logger = object;
+	char message[MESSAGE_SIZE];
+
+	(void)topic;
+
+	vsnprintf(message, sizeof(message), fmt, args);
+	logger->handler(logger->data, level, file, line, func, message);
+}
+
+static void impl_logt(void *object, enum spa_log_level level, const struct spa_log_topic *topic,
+		      const char *file, int line, const char *func, const char *fmt, ...)
+{
+	va_list args;
+
+	va_start(args, fmt);
+	impl_logtv(object, level, topic, file, line, func, fmt, args);
+	va_end(args);
+}
+
+static void impl_logv(void *object, enum spa_log_level level, const char *file, int line,
+		      const char *func, const char *fmt, va_list args)
+{
+	impl_logtv(object, level, NULL, file, line, func, fmt, args);
+}
+
+static void impl_log(void *object, enum spa_log_level level, const char *file, int line,
+		     const char *func, const char *fmt, ...)
+{
+	va_list args;
+
+	va_start(args, fmt);
+	impl_logtv(object, level, NULL, file, line, func, fmt, args);
+	va_end(args);
+}
+
+static void impl_topic_init(void *object, struct spa_log_topic *topic)
+{
+	/* Topics have no custom level, so the level of the log applies to all of them. */
+	(void)object;
+	(void)topic;
+}
+
+static const struct spa_log_methods methods = {
+	.version = SPA_VERSION_LOG_METHODS,
+	.log = impl_log,
+	.logv = impl_logv,
+	.logt = impl_logt,
+	.logtv = impl_logtv,
+	.topic_init = impl_topic_init,
+};
+
+struct spa_log *pipewire_rs_log_new(pipewire_rs_log_handler handler, void *data)
+{
+	struct logger *logger = calloc(1, sizeof(*logger));
+
+	if (logger == NULL)
+		return NULL;
+
+	logger->log.iface = SPA_INTERFACE_INIT(SPA_TYPE_INTERFACE_Log, SPA_VERSION_LOG, &methods, logger);
+	logger->log.level = SPA_LOG_LEVEL_WARN;
+	logger->handler = handler;
+	logger->data = data;
+
+	return &logger->log;
+}
+
+void pipewire_rs_log_free(struct spa_log *log)
+{
+	free(SPA_CONTAINER_OF(log, struct logger, log));
+}
@@ -0,0 +1,18 @@
+/* Copyright The pipewire-rs Contributors.
+ * SPDX-License-Identifier: MIT
+ */
+
+/* A `spa_log` implementation formatting the messages before passing them to a handler,
+ * as the variadic arguments of the log methods cannot be handled from Rust. */
+
+#include <spa/support/log.h>
+
+typedef void (*pipewire_rs_log_handler)(void *data, enum spa_log_level level, const char *file,
+					 int line, const char *func, const char *message);
+
+/* Create a logger calling `handler` with `data` for each message,
+ * returns NULL if it could not be allocated. */
+struct spa_log *pipewire_rs_log_new(pipewire_rs_log_handler handler, void *data);
+
+/* Free a logger created with `pipewire_rs_log_new()`. */
+void pipewire_rs_log_free(struct spa_log *log);
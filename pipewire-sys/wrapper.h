@@ -3,4 +3,5 @@
 #include <pipewire/extensions/metadata.h>
 #include <pipewire/extensions/profiler.h>
 #include <pipewire/extensions/protocol-native.h>
-#include <pipewire/extensions/session-manager.h>
\ No newline at end of file
+#include <pipewire/extensions/session-manager.h>
+#include "log.h"
@@ -8,8 +8,12 @@ use std::{cell::RefCell, collections::HashMap};
 use std::{rc::Rc, sync::Arc};
 use structopt::StructOpt;
 
+use pw::client::Client;
+use pw::device::Device;
+use pw::factory::Factory;
 use pw::link::Link;
 use pw::metadata::Metadata;
+use pw::module::Module;
 use pw::node::Node;
 use pw::port::Port;
 use pw::prelude::*;
@@ -119,7 +123,7 @@ fn monitor(remote: Option<String>) -> Result<()> {
                             .info(|info| {
                                 dbg!(info);
                             })
-                            .param(|seq, id, index, next| {
+                            .param(|seq, id, index, next, _param| {
                                 dbg!((seq, id, index, next));
                             })
                             .register();
@@ -133,7 +137,7 @@ fn monitor(remote: Option<String>) -> Result<()> {
                             .info(|info| {
                                 dbg!(info);
                             })
-                            .param(|seq, id, index, next| {
+                            .param(|seq, id, index, next, _param| {
                                 dbg!((seq, id, index, next));
                             })
                             .register();
@@ -156,20 +160,60 @@ fn monitor(remote: Option<String>) -> Result<()> {
                         dbg!(&obj.props);
                         let obj_listener = metadata
                             .add_listener_local()
-                            .property(|subject, key, type_, value| {
-                                dbg!((subject, key, type_, value));
+                            .property(|property| {
+                                dbg!(property);
                                 0
                             })
                             .register();
 
                         Some((Box::new(metadata), Box::new(obj_listener)))
                     }
-                    ObjectType::Module
-                    | ObjectType::Device
-                    | ObjectType::Factory
-                    | ObjectType::Client => {
-                        // TODO
-                        None
+                    ObjectType::Client => {
+                        let client: Client = registry.bind(obj).unwrap();
+                        let obj_listener = client
+                            .add_listener_local()
+                            .info(|info| {
+                                dbg!(info);
+                            })
+                            .register();
+
+                        Some((Box::new(client), Box::new(obj_listener)))
+                    }
+                    ObjectType::Device => {
+                        let device: Device = registry.bind(obj).unwrap();
+                        let obj_listener = device
+                            .add_listener_local()
+                            .info(|info| {
+                                dbg!(info);
+                            })
+                            .param(|seq, id, index, next, _param| {
+                                dbg!((seq, id, index, next));
+                            })
+                            .register();
+
+                        Some((Box::new(device), Box::new(obj_listener)))
+                    }
+                    ObjectType::Factory => {
+                        let factory: Factory = registry.bind(obj).unwrap();
+                        let obj_listener = factory
+                            .add_listener_local()
+                            .info(|info| {
+                                dbg!(info);
+                            })
+                            .register();
+
+                        Some((Box::new(factory), Box::new(obj_listener)))
+                    }
+                    ObjectType::Module => {
+                        let module: Module = registry.bind(obj).unwrap();
+                        let obj_listener = module
+                            .add_listener_local()
+                            .info(|info| {
+                                dbg!(info);
+                            })
+                            .register();
+
+                        Some((Box::new(module), Box::new(obj_listener)))
                     }
                     _ => {
                         dbg!(obj);
@@ -61,7 +61,7 @@ pub fn main() -> Result<(), pw::Error> {
         spa::Direction::Input,
         opt.target,
         pw::stream::StreamFlags::AUTOCONNECT | pw::stream::StreamFlags::MAP_BUFFERS,
-        &mut [],
+        &[],
     )?;
 
     println!("Connected stream");
@@ -1,6 +1,6 @@
 use super::stream::Stream;
 
-use crate::data::Data;
+use crate::data::{Data, Meta};
 use std::convert::TryFrom;
 use std::ptr::NonNull;
 
@@ -14,6 +14,10 @@ pub struct Buffer<'s, D> {
     /// An empty array of `Data`, that can be used to return an empty slice
     /// when a buffer has no data.
     empty_data: [Data; 0],
+
+    /// An empty array of `Meta`, that can be used to return an empty slice
+    /// when a buffer has no metadata.
+    empty_metas: [Meta; 0],
 }
 
 impl<D> Buffer<'_, D> {
@@ -25,9 +29,34 @@ impl<D> Buffer<'_, D> {
             buf,
             stream,
             empty_data: [],
+            empty_metas: [],
         })
     }
 
+    /// Get the underlying `spa_buffer`.
+    pub fn buffer(&self) -> &spa_sys::spa_buffer {
+        unsafe {
+            self.buf
+                .as_ref()
+                .buffer
+                .as_ref()
+                .expect("spa_buffer is NULL")
+        }
+    }
+
+    pub fn datas(&self) -> &[Data] {
+        let buffer: *mut spa_sys::spa_buffer = unsafe { self.buf.as_ref().buffer };
+
+        if !buffer.is_null() && unsafe { (*buffer).n_datas > 0 && !(*buffer).datas.is_null() } {
+            unsafe {
+                let datas = (*buffer).datas as *const Data;
+                std::slice::from_raw_parts(datas, usize::try_from((*buffer).n_datas).unwrap())
+            }
+        } else {
+            &self.empty_data
+        }
+    }
+
     pub fn datas_mut(&mut self) -> &mut [Data] {
         let buffer: *mut spa_sys::spa_buffer = unsafe { self.buf.as_ref().buffer };
 
@@ -44,6 +73,32 @@ impl<D> Buffer<'_, D> {
 
         slice_of_data
     }
+
+    pub fn metas(&self) -> &[Meta] {
+        let buffer: *mut spa_sys::spa_buffer = unsafe { self.buf.as_ref().buffer };
+
+        if !buffer.is_null() && unsafe { (*buffer).n_metas > 0 && !(*buffer).metas.is_null() } {
+            unsafe {
+                let metas = (*buffer).metas as *const Meta;
+                std::slice::from_raw_parts(metas, usize::try_from((*buffer).n_metas).unwrap())
+            }
+        } else {
+            &self.empty_metas
+        }
+    }
+
+    pub fn metas_mut(&mut self) -> &mut [Meta] {
+        let buffer: *mut spa_sys::spa_buffer = unsafe { self.buf.as_ref().buffer };
+
+        if !buffer.is_null() && unsafe { (*buffer).n_metas > 0 && !(*buffer).metas.is_null() } {
+            unsafe {
+                let metas = (*buffer).metas as *mut Meta;
+                std::slice::from_raw_parts_mut(metas, usize::try_from((*buffer).n_metas).unwrap())
+            }
+        } else {
+            &mut self.empty_metas
+        }
+    }
 }
 
 impl<D> Drop for Buffer<'_, D> {
@@ -0,0 +1,276 @@
+// Copyright The pipewire-rs Contributors.
+// SPDX-License-Identifier: MIT
+
+use bitflags::bitflags;
+use libc::c_void;
+use std::{convert::TryInto, fmt, mem};
+use std::{pin::Pin, ptr};
+
+use crate::{
+    permissions::Permission,
+    proxy::{Listener, Proxy, ProxyT},
+    types::ObjectType,
+};
+use spa::dict::{ForeignDict, ReadableDict};
+use spa::spa_interface_call_method;
+
+#[derive(Debug)]
+pub struct Client {
+    proxy: Proxy,
+}
+
+impl ProxyT for Client {
+    fn type_() -> ObjectType {
+        ObjectType::Client
+    }
+
+    fn upcast(self) -> Proxy {
+        self.proxy
+    }
+
+    fn upcast_ref(&self) -> &Proxy {
+        &self.proxy
+    }
+
+    unsafe fn from_proxy_unchecked(proxy: Proxy) -> Self
+    where
+        Self: Sized,
+    {
+        Self { proxy }
+    }
+}
+
+impl Client {
+    // TODO: add non-local version when we'll bind pw_thread_loop_start()
+    #[must_use]
+    pub fn add_listener_local(&self) -> ClientListenerLocalBuilder {
+        ClientListenerLocalBuilder {
+            client: self,
+            cbs: ListenerLocalCallbacks::default(),
+        }
+    }
+
+    /// Update the properties of the client
+    pub fn update_properties<D: ReadableDict>(&self, properties: &D) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_client_methods,
+                update_properties,
+                properties.get_dict_ptr()
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Get the permissions of the client
+    ///
+    /// The permissions are emitted with the `permissions` event.
+    ///
+    /// # Parameters
+    /// - `index`: the index of the first permission entry to return
+    /// - `num`: the maximum number of entries to return
+    pub fn get_permissions(&self, index: u32, num: u32) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_client_methods,
+                get_permissions,
+                index,
+                num
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Update the permissions of the client
+    ///
+    /// Each entry sets the permissions the client has on the global object with the entry id.
+    /// An entry with id `PW_ID_ANY` sets the default permissions.
+    pub fn update_permissions(&self, permissions: &[Permission]) -> spa::SpaResult {
+        let permissions: Vec<pw_sys::pw_permission> =
+            permissions.iter().map(Permission::as_raw).collect();
+
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_client_methods,
+                update_permissions,
+                permissions.len().try_into().unwrap(),
+                permissions.as_ptr()
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+}
+
+#[derive(Default)]
+struct ListenerLocalCallbacks {
+    info: Option<Box<dyn Fn(&ClientInfo)>>,
+    permissions: Option<Box<dyn Fn(u32, &[Permission])>>,
+}
+
+pub struct ClientListenerLocalBuilder<'a> {
+    client: &'a Client,
+    cbs: ListenerLocalCallbacks,
+}
+
+pub struct ClientInfo {
+    ptr: ptr::NonNull<pw_sys::pw_client_info>,
+    props: Option<ForeignDict>,
+}
+
+impl ClientInfo {
+    fn new(ptr: ptr::NonNull<pw_sys::pw_client_info>) -> Self {
+        let props_ptr = unsafe { ptr.as_ref().props };
+        let props = ptr::NonNull::new(props_ptr).map(|ptr| unsafe { ForeignDict::from_ptr(ptr) });
+
+        Self { ptr, props }
+    }
+
+    pub fn id(&self) -> u32 {
+        unsafe { self.ptr.as_ref().id }
+    }
+
+    pub fn change_mask(&self) -> ClientChangeMask {
+        let mask = unsafe { self.ptr.as_ref().change_mask };
+        ClientChangeMask::from_bits(mask).expect("invalid change_mask")
+    }
+
+    pub fn props(&self) -> Option<&ForeignDict> {
+        self.props.as_ref()
+    }
+}
+
+bitflags! {
+    pub struct ClientChangeMask: u64 {
+        const PROPS = pw_sys::PW_CLIENT_CHANGE_MASK_PROPS as u64;
+    }
+}
+
+impl fmt::Debug for ClientInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientInfo")
+            .field("id", &self.id())
+            .field("change-mask", &self.change_mask())
+            .field("props", &self.props())
+            .finish()
+    }
+}
+
+pub struct ClientListener {
+    // Need to stay allocated while the listener is registered
+    #[allow(dead_code)]
+    events: Pin<Box<pw_sys::pw_client_events>>,
+    listener: Pin<Box<spa_sys::spa_hook>>,
+    #[allow(dead_code)]
+    data: Box<ListenerLocalCallbacks>,
+}
+
+impl<'a> Listener for ClientListener {}
+
+impl<'a> Drop for ClientListener {
+    fn drop(&mut self) {
+        spa::hook::remove(*self.listener);
+    }
+}
+
+impl<'a> ClientListenerLocalBuilder<'a> {
+    /// Add info callback, called when the client info changes.
+    #[must_use]
+    pub fn info<F>(mut self, info: F) -> Self
+    where
+        F: Fn(&ClientInfo) + 'static,
+    {
+        self.cbs.info = Some(Box::new(info));
+        self
+    }
+
+    /// Add permissions callback, called with the permissions requested by [`Client::get_permissions`].
+    ///
+    /// Callback parameters: index, permissions.
+    ///
+    /// `index` is the index of the first permission entry, so that the entries
+    /// can be requested in several calls.
+    #[must_use]
+    pub fn permissions<F>(mut self, permissions: F) -> Self
+    where
+        F: Fn(u32, &[Permission]) + 'static,
+    {
+        self.cbs.permissions = Some(Box::new(permissions));
+        self
+    }
+
+    #[must_use]
+    pub fn register(self) -> ClientListener {
+        unsafe extern "C" fn client_events_info(
+            data: *mut c_void,
+            info: *const pw_sys::pw_client_info,
+        ) {
+            let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
+            let info = ptr::NonNull::new(info as *mut _).expect("info is NULL");
+            let info = ClientInfo::new(info);
+            callbacks.info.as_ref().unwrap()(&info);
+        }
+
+        unsafe extern "C" fn client_events_permissions(
+            data: *mut c_void,
+            index: u32,
+            n_permissions: u32,
+            permissions: *const pw_sys::pw_permission,
+        ) {
+            let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
+            let permissions: Vec<Permission> = if permissions.is_null() {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(permissions, n_permissions as usize)
+                    .iter()
+                    .map(|p| Permission::from_raw(*p))
+                    .collect()
+            };
+            callbacks.permissions.as_ref().unwrap()(index, &permissions);
+        }
+
+        let e = unsafe {
+            let mut e: Pin<Box<pw_sys::pw_client_events>> = Box::pin(mem::zeroed());
+            e.version = pw_sys::PW_VERSION_CLIENT_EVENTS;
+
+            if self.cbs.info.is_some() {
+                e.info = Some(client_events_info);
+            }
+            if self.cbs.permissions.is_some() {
+                e.permissions = Some(client_events_permissions);
+            }
+
+            e
+        };
+
+        let (listener, data) = unsafe {
+            let client = &self.client.proxy.as_ptr();
+
+            let data = Box::into_raw(Box::new(self.cbs));
+            let mut listener: Pin<Box<spa_sys::spa_hook>> = Box::pin(mem::zeroed());
+            let listener_ptr: *mut spa_sys::spa_hook = listener.as_mut().get_unchecked_mut();
+
+            spa_interface_call_method!(
+                client,
+                pw_sys::pw_client_methods,
+                add_listener,
+                listener_ptr.cast(),
+                e.as_ref().get_ref(),
+                data as *mut _
+            );
+
+            (listener, Box::from_raw(data))
+        };
+
+        ClientListener {
+            events: e,
+            listener,
+            data,
+        }
+    }
+}
@@ -1,7 +1,18 @@
 // Copyright The pipewire-rs Contributors.
 // SPDX-License-Identifier: MIT
 
-use std::{os::unix::prelude::RawFd, ptr};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    ffi::CString,
+    fmt,
+    os::unix::prelude::{OsStrExt, RawFd},
+    path::Path,
+    ptr,
+};
+
+use spa::{dict::WritableDict, result::SpaResult};
 
 use crate::core_::Core;
 use crate::error::Error;
@@ -13,7 +24,23 @@ pub struct Context<T: IsLoop + Clone> {
     ptr: ptr::NonNull<pw_sys::pw_context>,
     /// Store the loop here, so that the loop is not dropped before the context, which may lead to
     /// undefined behaviour.
-    _loop: T,
+    loop_: T,
+    /// Data attached with `set_object_data()`, dropped after the context has been destroyed.
+    object_data: ObjectData,
+}
+
+/// The data attached to a context, by key.
+#[derive(Default)]
+struct ObjectData {
+    data: RefCell<HashMap<&'static str, Box<dyn Any>>>,
+    /// Data that has been replaced, which may still be borrowed from `get_object_data()`.
+    replaced: RefCell<Vec<Box<dyn Any>>>,
+}
+
+impl fmt::Debug for ObjectData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.data.borrow().keys()).finish()
+    }
 }
 
 impl<T: IsLoop + Clone> Context<T> {
@@ -26,7 +53,8 @@ impl<T: IsLoop + Clone> Context<T> {
 
         Ok(Context {
             ptr: context,
-            _loop: loop_.clone(),
+            loop_: loop_.clone(),
+            object_data: ObjectData::default(),
         })
     }
 
@@ -38,10 +66,61 @@ impl<T: IsLoop + Clone> Context<T> {
         Self::new_internal(loop_, Some(properties))
     }
 
+    /// Create a context reading its configuration from the config file at `path`
+    /// instead of the standard location.
+    ///
+    /// `path` should be absolute. It is set as the `config.name` property of the context.
+    pub fn new_with_conf_path(
+        loop_: &T,
+        path: &Path,
+        mut properties: Properties,
+    ) -> Result<Self, Error> {
+        properties.insert(
+            crate::keys::CONFIG_NAME.as_bytes(),
+            path.as_os_str().as_bytes(),
+        );
+
+        Self::new_internal(loop_, Some(properties))
+    }
+
     fn as_ptr(&self) -> *mut pw_sys::pw_context {
         self.ptr.as_ptr()
     }
 
+    /// Get the loop the context was created with.
+    pub fn get_loop(&self) -> &T {
+        &self.loop_
+    }
+
+    /// Attach `data` to the context with `key`.
+    ///
+    /// The data is only stored on the Rust side and is not visible to C code through `pw_context_get_object()`,
+    /// so keys cannot conflict with the objects registered by PipeWire or its modules.
+    ///
+    /// Data previously attached with the same key is replaced, but as it may still be borrowed,
+    /// it is only dropped along with the rest of the data, after the context has been destroyed.
+    pub fn set_object_data<D: 'static>(&self, key: &'static str, data: D) {
+        let previous = self
+            .object_data
+            .data
+            .borrow_mut()
+            .insert(key, Box::new(data));
+        if let Some(previous) = previous {
+            self.object_data.replaced.borrow_mut().push(previous);
+        }
+    }
+
+    /// Get the data attached to the context with `key` by [`set_object_data`](Self::set_object_data).
+    ///
+    /// Returns `None` if there is no data attached with `key`, or if it is not of type `D`.
+    pub fn get_object_data<D: 'static>(&self, key: &str) -> Option<&D> {
+        let data = self.object_data.data.borrow();
+        let data: *const D = data.get(key)?.downcast_ref()?;
+        // Safety: The data is boxed so it does not move when the map changes,
+        // and it is never dropped before the context.
+        Some(unsafe { &*data })
+    }
+
     pub fn connect(&self, properties: Option<Properties>) -> Result<Core, Error> {
         let properties = properties.map_or(ptr::null_mut(), |p| p.into_raw());
 
@@ -53,6 +132,30 @@ impl<T: IsLoop + Clone> Context<T> {
         }
     }
 
+    /// Apply a configuration section to the context.
+    ///
+    /// `value` is parsed as the content of the config section named `section`,
+    /// such as `context.properties`, `context.modules` or `context.objects`,
+    /// as if it was read from a config file.
+    ///
+    /// This allows overriding the configuration without writing a config file to the filesystem.
+    ///
+    /// # Panics
+    /// If `section` or `value` contains a null byte.
+    pub fn set_conf_section(&self, section: &str, value: &str) -> Result<(), Error> {
+        let conf = crate::properties! {
+            section => value
+        };
+        let section = CString::new(section).expect("Null byte in section parameter");
+
+        let res = unsafe {
+            pw_sys::pw_context_parse_conf_section(self.as_ptr(), conf.as_ptr(), section.as_ptr())
+        };
+
+        SpaResult::from_c(res).into_sync_result()?;
+        Ok(())
+    }
+
     pub fn connect_fd(&self, fd: RawFd, properties: Option<Properties>) -> Result<Core, Error> {
         let properties = properties.map_or(ptr::null_mut(), |p| p.into_raw());
 
@@ -4,16 +4,19 @@
 use bitflags::bitflags;
 use libc::{c_char, c_void};
 use std::{
+    cell::Cell,
     ffi::{CStr, CString},
+    os::unix::prelude::RawFd,
     rc::Rc,
+    time::Duration,
 };
 use std::{fmt, mem, ptr};
 use std::{ops::Deref, pin::Pin};
 
 use crate::{
     proxy::{Proxy, ProxyT},
-    registry::Registry,
-    Error,
+    registry::{self, GlobalObject, Registry},
+    Error, LoopRef,
 };
 use spa::{dict::ForeignDict, result::SpaResult, spa_interface_call_method, AsyncSeq};
 
@@ -46,7 +49,7 @@ pub struct CoreInner {
 }
 
 impl CoreInner {
-    fn from_ptr(ptr: ptr::NonNull<pw_sys::pw_core>) -> Self {
+    pub(crate) fn from_ptr(ptr: ptr::NonNull<pw_sys::pw_core>) -> Self {
         Self { ptr }
     }
 
@@ -69,6 +72,20 @@ impl CoreInner {
         }
     }
 
+    /// Monitor the global objects of the remote, without having to manage a [`Registry`].
+    ///
+    /// PipeWire only announces globals through the registry, so the returned
+    /// [`GlobalListener`] creates one and keeps it alive for as long as it is registered.
+    /// Use [`get_registry`](Self::get_registry) instead if the globals need to be bound.
+    #[must_use]
+    pub fn add_global_listener_local(&self) -> GlobalListenerLocalBuilder {
+        GlobalListenerLocalBuilder {
+            core: self,
+            global_add: None,
+            global_remove: None,
+        }
+    }
+
     pub fn get_registry(&self) -> Result<Registry, Error> {
         let registry = unsafe {
             spa_interface_call_method!(
@@ -99,6 +116,54 @@ impl CoreInner {
         Ok(res)
     }
 
+    /// Block until the server has processed all the requests previously sent by this core.
+    ///
+    /// This issues a [`sync`](Self::sync) and iterates the main loop of the core's context
+    /// until the matching `done` event is received, dispatching every other pending event
+    /// in the process.
+    ///
+    /// As the loop is iterated from within this call, it must not be used from a callback
+    /// running on that loop, nor while the loop is run from another thread.
+    pub fn roundtrip(&self) -> Result<(), Error> {
+        let done = Rc::new(Cell::new(false));
+        let error = Rc::new(Cell::new(None));
+
+        let pending = self.sync(0)?;
+
+        let done_clone = done.clone();
+        let error_clone = error.clone();
+        let _listener = self
+            .add_listener_local()
+            .done(move |id, seq| {
+                if id == PW_ID_CORE && seq == pending {
+                    done_clone.set(true);
+                }
+            })
+            .error(move |id, _seq, res, _message| {
+                // Errors on the core itself, such as a lost connection, mean `done` will never come.
+                if id == PW_ID_CORE && res < 0 {
+                    error_clone.set(Some(res));
+                }
+            })
+            .register();
+
+        let loop_ = unsafe {
+            let context = pw_sys::pw_core_get_context(self.as_ptr());
+            &*(pw_sys::pw_context_get_main_loop(context) as *const LoopRef)
+        };
+
+        while !done.get() {
+            if let Some(res) = error.get() {
+                SpaResult::from_c(res).into_sync_result()?;
+            }
+
+            let res = loop_.iterate(Duration::from_secs(1));
+            SpaResult::from_c(res).into_sync_result()?;
+        }
+
+        Ok(())
+    }
+
     /// Create a new object on the PipeWire server from a factory.
     ///
     /// You will need specify what type you are expecting to be constructed by either using type inference or the
@@ -199,7 +264,9 @@ struct ListenerLocalCallbacks {
     done: Option<Box<dyn Fn(u32, AsyncSeq)>>,
     #[allow(clippy::type_complexity)]
     error: Option<Box<dyn Fn(u32, i32, i32, &str)>>, // TODO: return a proper Error enum?
-                                                     // TODO: ping, remove_id, bound_id, add_mem, remove_mem
+    add_mem: Option<Box<dyn Fn(u32, u32, RawFd, u32)>>,
+    remove_mem: Option<Box<dyn Fn(u32)>>,
+    // TODO: ping, remove_id, bound_id
 }
 
 pub struct ListenerLocalBuilder<'a> {
@@ -256,6 +323,33 @@ impl<'a> ListenerLocalBuilder<'a> {
         self
     }
 
+    /// Add add_mem callback, called when the server shares a memory region with the client.
+    ///
+    /// Callback parameters: id, type, fd, flags.
+    ///
+    /// - `id`: the id of the memory, used to refer to it in buffers and in the `remove_mem` event
+    /// - `type`: the type of the memory, one of the `SPA_DATA_*` types
+    /// - `fd`: the file descriptor of the memory, which stays owned by PipeWire
+    /// - `flags`: the `SPA_DATA_FLAG_*` flags of the memory
+    #[must_use]
+    pub fn add_mem<F>(mut self, add_mem: F) -> Self
+    where
+        F: Fn(u32, u32, RawFd, u32) + 'static,
+    {
+        self.cbs.add_mem = Some(Box::new(add_mem));
+        self
+    }
+
+    /// Add remove_mem callback, called when the memory with the given id is no longer shared.
+    #[must_use]
+    pub fn remove_mem<F>(mut self, remove_mem: F) -> Self
+    where
+        F: Fn(u32) + 'static,
+    {
+        self.cbs.remove_mem = Some(Box::new(remove_mem));
+        self
+    }
+
     #[must_use]
     pub fn register(self) -> Listener {
         unsafe extern "C" fn core_events_info(
@@ -284,6 +378,22 @@ impl<'a> ListenerLocalBuilder<'a> {
             callbacks.error.as_ref().unwrap()(id, seq, res, message);
         }
 
+        unsafe extern "C" fn core_events_add_mem(
+            data: *mut c_void,
+            id: u32,
+            type_: u32,
+            fd: i32,
+            flags: u32,
+        ) {
+            let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
+            callbacks.add_mem.as_ref().unwrap()(id, type_, fd, flags);
+        }
+
+        unsafe extern "C" fn core_events_remove_mem(data: *mut c_void, id: u32) {
+            let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
+            callbacks.remove_mem.as_ref().unwrap()(id);
+        }
+
         let e = unsafe {
             let mut e: Pin<Box<pw_sys::pw_core_events>> = Box::pin(mem::zeroed());
             e.version = pw_sys::PW_VERSION_CORE_EVENTS;
@@ -297,6 +407,12 @@ impl<'a> ListenerLocalBuilder<'a> {
             if self.cbs.error.is_some() {
                 e.error = Some(core_events_error);
             }
+            if self.cbs.add_mem.is_some() {
+                e.add_mem = Some(core_events_add_mem);
+            }
+            if self.cbs.remove_mem.is_some() {
+                e.remove_mem = Some(core_events_remove_mem);
+            }
 
             e
         };
@@ -330,6 +446,70 @@ impl<'a> ListenerLocalBuilder<'a> {
     }
 }
 
+#[allow(clippy::type_complexity)]
+pub struct GlobalListenerLocalBuilder<'a> {
+    core: &'a CoreInner,
+    global_add: Option<Box<dyn Fn(&GlobalObject<ForeignDict>)>>,
+    global_remove: Option<Box<dyn Fn(u32)>>,
+}
+
+pub struct GlobalListener {
+    // Fields are dropped in declaration order, so the listener is removed
+    // before the registry proxy is destroyed.
+    #[allow(dead_code)]
+    listener: registry::Listener,
+    #[allow(dead_code)]
+    registry: Registry,
+}
+
+impl GlobalListener {
+    pub fn unregister(self) {
+        // Consuming the listener will call drop()
+    }
+}
+
+impl<'a> GlobalListenerLocalBuilder<'a> {
+    /// Add global_add callback, called when a new global object is announced by the remote.
+    ///
+    /// The [`GlobalObject`] provides the id, permissions, type, version and properties of the global.
+    #[must_use]
+    pub fn global_add<F>(mut self, global_add: F) -> Self
+    where
+        F: Fn(&GlobalObject<ForeignDict>) + 'static,
+    {
+        self.global_add = Some(Box::new(global_add));
+        self
+    }
+
+    /// Add global_remove callback, called with the id of a global object removed from the remote.
+    #[must_use]
+    pub fn global_remove<F>(mut self, global_remove: F) -> Self
+    where
+        F: Fn(u32) + 'static,
+    {
+        self.global_remove = Some(Box::new(global_remove));
+        self
+    }
+
+    /// Register the listener.
+    ///
+    /// Fails if the registry backing the listener could not be created.
+    pub fn register(self) -> Result<GlobalListener, Error> {
+        let registry = self.core.get_registry()?;
+
+        let mut builder = registry.add_listener_local();
+        if let Some(global_add) = self.global_add {
+            builder = builder.global(global_add);
+        }
+        if let Some(global_remove) = self.global_remove {
+            builder = builder.global_remove(global_remove);
+        }
+        let listener = builder.register();
+
+        Ok(GlobalListener { listener, registry })
+    }
+}
+
 pub struct Info {
     ptr: ptr::NonNull<pw_sys::pw_core_info>,
     /// Can contain a Dict wrapping the raw spa_dict at (*ptr).props.
@@ -1,12 +1,69 @@
+use bitflags::bitflags;
 use std::{convert::TryFrom, os::unix::io::RawFd};
 
 #[repr(transparent)]
 pub struct Data(spa_sys::spa_data);
 #[repr(transparent)]
 pub struct Chunk(spa_sys::spa_chunk);
+#[repr(transparent)]
+pub struct Meta(spa_sys::spa_meta);
+
+/// The type of the memory of a [`Data`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DataType {
+    /// No memory.
+    Invalid,
+    /// Memory is directly accessible through a pointer.
+    MemPtr,
+    /// Memory is a generic file descriptor which can be mmapped.
+    MemFd,
+    /// Memory is a DMA-BUF file descriptor.
+    DmaBuf,
+    /// Memory is identified by an id.
+    MemId,
+}
+
+impl TryFrom<u32> for DataType {
+    type Error = u32;
+
+    /// Convert a raw `SPA_DATA_*` value, returning it back if it is unknown.
+    fn try_from(type_: u32) -> Result<Self, Self::Error> {
+        match type_ {
+            spa_sys::SPA_DATA_Invalid => Ok(DataType::Invalid),
+            spa_sys::SPA_DATA_MemPtr => Ok(DataType::MemPtr),
+            spa_sys::SPA_DATA_MemFd => Ok(DataType::MemFd),
+            spa_sys::SPA_DATA_DmaBuf => Ok(DataType::DmaBuf),
+            spa_sys::SPA_DATA_MemId => Ok(DataType::MemId),
+            _ => Err(type_),
+        }
+    }
+}
 
 impl Data {
-    pub fn get_mut(&mut self) -> &mut [u8] {
+    /// Get the memory of the data, as `max_size` bytes.
+    ///
+    /// Returns an empty slice if the memory is not mapped.
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.0.data.is_null() {
+            return &[];
+        }
+
+        unsafe {
+            std::slice::from_raw_parts(
+                self.0.data as *const u8,
+                usize::try_from(self.0.maxsize).unwrap(),
+            )
+        }
+    }
+
+    /// Get the memory of the data mutably, as `max_size` bytes.
+    ///
+    /// Returns an empty slice if the memory is not mapped.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        if self.0.data.is_null() {
+            return &mut [];
+        }
+
         unsafe {
             std::slice::from_raw_parts_mut(
                 self.0.data as *mut u8,
@@ -15,6 +72,29 @@ impl Data {
         }
     }
 
+    #[deprecated(note = "use as_bytes_mut() instead")]
+    pub fn get_mut(&mut self) -> &mut [u8] {
+        self.as_bytes_mut()
+    }
+
+    /// Get the valid part of the memory of the data, as described by its chunk.
+    ///
+    /// Returns an empty slice if the memory is not mapped or if there is no chunk.
+    pub fn valid_data(&self) -> &[u8] {
+        if self.0.chunk.is_null() {
+            return &[];
+        }
+
+        let bytes = self.as_bytes();
+        let chunk = unsafe { &*self.0.chunk };
+        let offset = usize::try_from(chunk.offset).unwrap().min(bytes.len());
+        let size = usize::try_from(chunk.size)
+            .unwrap()
+            .min(bytes.len() - offset);
+
+        &bytes[offset..offset + size]
+    }
+
     pub fn chunk(&mut self) -> &mut Chunk {
         assert_ne!(self.0.chunk, std::ptr::null_mut());
         unsafe {
@@ -23,8 +103,9 @@ impl Data {
         }
     }
 
-    pub fn type_(&self) -> u32 {
-        self.0.type_
+    /// Get the type of the memory, or the raw type if it is unknown.
+    pub fn type_(&self) -> Result<DataType, u32> {
+        DataType::try_from(self.0.type_)
     }
 
     pub fn flags(&self) -> u32 {
@@ -50,7 +131,32 @@ impl Data {
     }
 }
 
+bitflags! {
+    /// Flags of a [`Chunk`].
+    pub struct ChunkFlags: i32 {
+        /// The chunk data is corrupted in some way.
+        const CORRUPTED = spa_sys::SPA_CHUNK_FLAG_CORRUPTED as i32;
+    }
+}
+
 impl Chunk {
+    pub fn size(&self) -> u32 {
+        self.0.size
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.0.offset
+    }
+
+    pub fn stride(&self) -> i32 {
+        self.0.stride
+    }
+
+    /// Get the flags of the chunk, ignoring unknown ones.
+    pub fn flags(&self) -> ChunkFlags {
+        ChunkFlags::from_bits_truncate(self.0.flags)
+    }
+
     pub fn size_mut(&mut self) -> &mut u32 {
         &mut self.0.size
     }
@@ -61,3 +167,43 @@ impl Chunk {
         &mut self.0.stride
     }
 }
+
+impl Meta {
+    /// Get the payload of the meta.
+    ///
+    /// Returns an empty slice if the meta has no data.
+    pub fn data(&self) -> &[u8] {
+        if self.0.data.is_null() {
+            return &[];
+        }
+
+        unsafe {
+            std::slice::from_raw_parts(
+                self.0.data as *const u8,
+                usize::try_from(self.0.size).unwrap(),
+            )
+        }
+    }
+
+    /// Get the payload of the meta, for writing.
+    pub fn get_mut(&mut self) -> &mut [u8] {
+        if self.0.data.is_null() {
+            return &mut [];
+        }
+
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.0.data as *mut u8,
+                usize::try_from(self.0.size).unwrap(),
+            )
+        }
+    }
+
+    pub fn type_(&self) -> u32 {
+        self.0.type_
+    }
+
+    pub fn size(&self) -> u32 {
+        self.0.size
+    }
+}
@@ -0,0 +1,413 @@
+// Copyright The pipewire-rs Contributors.
+// SPDX-License-Identifier: MIT
+
+use bitflags::bitflags;
+use libc::c_void;
+use std::pin::Pin;
+use std::{convert::TryInto, ptr};
+use std::{fmt, mem};
+
+use crate::{
+    proxy::{Listener, Proxy, ProxyT},
+    types::ObjectType,
+};
+use spa::dict::ForeignDict;
+use spa::param::ParamType;
+use spa::pod::{
+    builder::PodBuilder, deserialize::PodDeserializer, Object, Pod, Property, PropertyFlags, Value,
+};
+use spa::spa_interface_call_method;
+
+#[derive(Debug)]
+pub struct Device {
+    proxy: Proxy,
+}
+
+impl ProxyT for Device {
+    fn type_() -> ObjectType {
+        ObjectType::Device
+    }
+
+    fn upcast(self) -> Proxy {
+        self.proxy
+    }
+
+    fn upcast_ref(&self) -> &Proxy {
+        &self.proxy
+    }
+
+    unsafe fn from_proxy_unchecked(proxy: Proxy) -> Self
+    where
+        Self: Sized,
+    {
+        Self { proxy }
+    }
+}
+
+impl Device {
+    // TODO: add non-local version when we'll bind pw_thread_loop_start()
+    #[must_use]
+    pub fn add_listener_local(&self) -> DeviceListenerLocalBuilder {
+        DeviceListenerLocalBuilder {
+            device: self,
+            cbs: ListenerLocalCallbacks::default(),
+        }
+    }
+
+    /// Subscribe to parameter changes
+    ///
+    /// Automatically emit `param` events for the given ids when they are changed
+    pub fn subscribe_params(&self, ids: &[ParamType]) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_device_methods,
+                subscribe_params,
+                ids.as_ptr() as *mut _,
+                ids.len().try_into().unwrap()
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Enumerate device parameters
+    ///
+    /// Start enumeration of device parameters. For each param, a `param` event will be emitted.
+    ///
+    /// # Parameters
+    /// - `seq`: a sequence number to place in the reply
+    /// - `id`: the parameter id to enum, or `SPA_PARAM_List` for all
+    /// - `index`: the index of the first param to return
+    /// - `num`: the maximum number of params to return
+    /// - `filter`: a param filter, or `None`
+    pub fn enum_params(
+        &self,
+        seq: i32,
+        id: ParamType,
+        index: u32,
+        num: u32,
+        filter: Option<&Pod>,
+    ) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_device_methods,
+                enum_params,
+                seq,
+                id.as_raw(),
+                index,
+                num,
+                filter.map_or(ptr::null(), |f| f.as_raw_ptr())
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Set a parameter on the device
+    ///
+    /// # Parameters
+    /// - `id`: the parameter id to set
+    /// - `flags`: extra parameter flags
+    /// - `param`: the parameter to set
+    pub fn set_param(&self, id: ParamType, flags: u32, param: &Pod) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_device_methods,
+                set_param,
+                id.as_raw(),
+                flags,
+                param.as_raw_ptr()
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Request the active profile of the device
+    ///
+    /// The profile is emitted with the `param` event, using `seq` as sequence number
+    /// and `SPA_PARAM_Profile` as id.
+    pub fn get_profile(&self, seq: i32) -> spa::SpaResult {
+        self.enum_params(seq, ParamType::PROFILE, 0, 1, None)
+    }
+
+    /// Switch the device to the profile with the given index
+    ///
+    /// The available profiles and their indexes can be enumerated with the
+    /// `SPA_PARAM_EnumProfile` param.
+    /// If `save` is `true`, the session manager is asked to remember the profile
+    /// and restore it the next time the device appears.
+    pub fn set_profile(&self, index: u32, save: bool) -> spa::SpaResult {
+        let profile = Value::Object(Object {
+            type_: spa_sys::SPA_TYPE_OBJECT_ParamProfile,
+            id: spa_sys::SPA_PARAM_Profile,
+            properties: vec![
+                Property {
+                    key: spa_sys::SPA_PARAM_PROFILE_index,
+                    flags: PropertyFlags::empty(),
+                    value: Value::Int(index as i32),
+                },
+                Property {
+                    key: spa_sys::SPA_PARAM_PROFILE_save,
+                    flags: PropertyFlags::empty(),
+                    value: Value::Bool(save),
+                },
+            ],
+        });
+
+        self.set_param_value(ParamType::PROFILE, &profile)
+    }
+
+    /// Switch the route of the given device to the route with the given index
+    ///
+    /// The available routes and their indexes can be enumerated with the
+    /// `SPA_PARAM_EnumRoute` param.
+    ///
+    /// # Parameters
+    /// - `index`: the index of the route
+    /// - `device`: the device id of the route, as found in the route param
+    /// - `props`: an optional `SPA_TYPE_OBJECT_Props` pod, to set the volume or mute state of the route
+    /// - `save`: whether the session manager should remember the route
+    pub fn set_route(
+        &self,
+        index: u32,
+        device: u32,
+        props: Option<&Pod>,
+        save: bool,
+    ) -> spa::SpaResult {
+        let mut properties = vec![
+            Property {
+                key: spa_sys::SPA_PARAM_ROUTE_index,
+                flags: PropertyFlags::empty(),
+                value: Value::Int(index as i32),
+            },
+            Property {
+                key: spa_sys::SPA_PARAM_ROUTE_device,
+                flags: PropertyFlags::empty(),
+                value: Value::Int(device as i32),
+            },
+        ];
+
+        if let Some(props) = props {
+            let props = unsafe {
+                PodDeserializer::deserialize_ptr::<Value>(ptr::NonNull::new_unchecked(
+                    props.as_raw_ptr(),
+                ))
+            };
+            let props = match props {
+                Ok(props) => props,
+                Err(_) => return spa::SpaResult::from_c(-libc::EINVAL),
+            };
+
+            properties.push(Property {
+                key: spa_sys::SPA_PARAM_ROUTE_props,
+                flags: PropertyFlags::empty(),
+                value: props,
+            });
+        }
+
+        properties.push(Property {
+            key: spa_sys::SPA_PARAM_ROUTE_save,
+            flags: PropertyFlags::empty(),
+            value: Value::Bool(save),
+        });
+
+        let route = Value::Object(Object {
+            type_: spa_sys::SPA_TYPE_OBJECT_ParamRoute,
+            id: spa_sys::SPA_PARAM_Route,
+            properties,
+        });
+
+        self.set_param_value(ParamType::ROUTE, &route)
+    }
+
+    /// Serialize `value` and set it as the `id` param of the device.
+    fn set_param_value(&self, id: ParamType, value: &Value) -> spa::SpaResult {
+        let mut builder = PodBuilder::new();
+        builder.push_pod(value).expect("Failed to serialize param");
+
+        self.set_param(id, 0, &builder.build_owned())
+    }
+}
+
+#[derive(Default)]
+struct ListenerLocalCallbacks {
+    info: Option<Box<dyn Fn(&DeviceInfo)>>,
+    #[allow(clippy::type_complexity)]
+    param: Option<Box<dyn Fn(i32, ParamType, u32, u32, Option<&Pod>)>>,
+}
+
+pub struct DeviceListenerLocalBuilder<'a> {
+    device: &'a Device,
+    cbs: ListenerLocalCallbacks,
+}
+
+pub struct DeviceInfo {
+    ptr: ptr::NonNull<pw_sys::pw_device_info>,
+    props: Option<ForeignDict>,
+}
+
+impl DeviceInfo {
+    fn new(ptr: ptr::NonNull<pw_sys::pw_device_info>) -> Self {
+        let props_ptr = unsafe { ptr.as_ref().props };
+        let props = ptr::NonNull::new(props_ptr).map(|ptr| unsafe { ForeignDict::from_ptr(ptr) });
+
+        Self { ptr, props }
+    }
+
+    pub fn id(&self) -> u32 {
+        unsafe { self.ptr.as_ref().id }
+    }
+
+    pub fn change_mask(&self) -> DeviceChangeMask {
+        let mask = unsafe { self.ptr.as_ref().change_mask };
+        DeviceChangeMask::from_bits(mask).expect("invalid change_mask")
+    }
+
+    pub fn props(&self) -> Option<&ForeignDict> {
+        self.props.as_ref()
+    }
+
+    /// Get the number of params the device has.
+    pub fn n_params(&self) -> u32 {
+        unsafe { self.ptr.as_ref().n_params }
+    }
+}
+
+bitflags! {
+    pub struct DeviceChangeMask: u64 {
+        const PROPS = pw_sys::PW_DEVICE_CHANGE_MASK_PROPS as u64;
+        const PARAMS = pw_sys::PW_DEVICE_CHANGE_MASK_PARAMS as u64;
+    }
+}
+
+impl fmt::Debug for DeviceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceInfo")
+            .field("id", &self.id())
+            .field("change-mask", &self.change_mask())
+            .field("n-params", &self.n_params())
+            .field("props", &self.props())
+            .finish()
+    }
+}
+
+pub struct DeviceListener {
+    // Need to stay allocated while the listener is registered
+    #[allow(dead_code)]
+    events: Pin<Box<pw_sys::pw_device_events>>,
+    listener: Pin<Box<spa_sys::spa_hook>>,
+    #[allow(dead_code)]
+    data: Box<ListenerLocalCallbacks>,
+}
+
+impl<'a> Listener for DeviceListener {}
+
+impl<'a> Drop for DeviceListener {
+    fn drop(&mut self) {
+        spa::hook::remove(*self.listener);
+    }
+}
+
+impl<'a> DeviceListenerLocalBuilder<'a> {
+    /// Add info callback, called when the device info changes.
+    ///
+    /// Device proxies are not notified of the objects managed by the device.
+    /// Instead, the nodes created by a device can be found by their
+    /// [`DEVICE_ID`](crate::keys::DEVICE_ID) property, matching [`DeviceInfo::id`].
+    #[must_use]
+    pub fn info<F>(mut self, info: F) -> Self
+    where
+        F: Fn(&DeviceInfo) + 'static,
+    {
+        self.cbs.info = Some(Box::new(info));
+        self
+    }
+
+    /// Add param callback.
+    ///
+    /// Callback parameters: seq, id, index, next, param.
+    ///
+    /// `param` may be `None` if the device did not provide a param,
+    /// for example once all the params requested by [`Device::enum_params`] have been emitted.
+    #[must_use]
+    pub fn param<F>(mut self, param: F) -> Self
+    where
+        F: Fn(i32, ParamType, u32, u32, Option<&Pod>) + 'static,
+    {
+        self.cbs.param = Some(Box::new(param));
+        self
+    }
+
+    #[must_use]
+    pub fn register(self) -> DeviceListener {
+        unsafe extern "C" fn device_events_info(
+            data: *mut c_void,
+            info: *const pw_sys::pw_device_info,
+        ) {
+            let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
+            let info = ptr::NonNull::new(info as *mut _).expect("info is NULL");
+            let info = DeviceInfo::new(info);
+            callbacks.info.as_ref().unwrap()(&info);
+        }
+
+        unsafe extern "C" fn device_events_param(
+            data: *mut c_void,
+            seq: i32,
+            id: u32,
+            index: u32,
+            next: u32,
+            param: *const spa_sys::spa_pod,
+        ) {
+            let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
+            let param = if !param.is_null() {
+                Some(Pod::from_raw(param))
+            } else {
+                None
+            };
+            callbacks.param.as_ref().unwrap()(seq, ParamType::from_raw(id), index, next, param);
+        }
+
+        let e = unsafe {
+            let mut e: Pin<Box<pw_sys::pw_device_events>> = Box::pin(mem::zeroed());
+            e.version = pw_sys::PW_VERSION_DEVICE_EVENTS;
+
+            if self.cbs.info.is_some() {
+                e.info = Some(device_events_info);
+            }
+            if self.cbs.param.is_some() {
+                e.param = Some(device_events_param);
+            }
+
+            e
+        };
+
+        let (listener, data) = unsafe {
+            let device = &self.device.proxy.as_ptr();
+
+            let data = Box::into_raw(Box::new(self.cbs));
+            let mut listener: Pin<Box<spa_sys::spa_hook>> = Box::pin(mem::zeroed());
+            let listener_ptr: *mut spa_sys::spa_hook = listener.as_mut().get_unchecked_mut();
+
+            spa_interface_call_method!(
+                device,
+                pw_sys::pw_device_methods,
+                add_listener,
+                listener_ptr.cast(),
+                e.as_ref().get_ref(),
+                data as *mut _
+            );
+
+            (listener, Box::from_raw(data))
+        };
+
+        DeviceListener {
+            events: e,
+            listener,
+            data,
+        }
+    }
+}
@@ -0,0 +1,396 @@
+// Copyright The pipewire-rs Contributors.
+// SPDX-License-Identifier: MIT
+
+//! Pipewire Filter
+
+use std::{
+    ffi::{CStr, CString},
+    fmt, mem,
+    os::raw::{c_char, c_void},
+    pin::Pin,
+    ptr,
+};
+
+use bitflags::bitflags;
+use spa::io::IoPosition;
+use spa::pod::Pod;
+use spa::result::SpaResult;
+
+use crate::{error::Error, Core, Properties};
+
+/// The state of a [`Filter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterState {
+    Error(String),
+    Unconnected,
+    Connecting,
+    Paused,
+    Streaming,
+}
+
+impl FilterState {
+    pub(crate) fn from_raw(state: pw_sys::pw_filter_state, error: *const c_char) -> Self {
+        match state {
+            pw_sys::pw_filter_state_PW_FILTER_STATE_UNCONNECTED => FilterState::Unconnected,
+            pw_sys::pw_filter_state_PW_FILTER_STATE_CONNECTING => FilterState::Connecting,
+            pw_sys::pw_filter_state_PW_FILTER_STATE_PAUSED => FilterState::Paused,
+            pw_sys::pw_filter_state_PW_FILTER_STATE_STREAMING => FilterState::Streaming,
+            _ => {
+                let error = if error.is_null() {
+                    "".to_string()
+                } else {
+                    unsafe { CStr::from_ptr(error).to_string_lossy().to_string() }
+                };
+
+                FilterState::Error(error)
+            }
+        }
+    }
+}
+
+impl fmt::Display for FilterState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterState::Error(error) => write!(f, "error: {}", error),
+            FilterState::Unconnected => f.write_str("unconnected"),
+            FilterState::Connecting => f.write_str("connecting"),
+            FilterState::Paused => f.write_str("paused"),
+            FilterState::Streaming => f.write_str("streaming"),
+        }
+    }
+}
+
+/// A wrapper around the pipewire filter interface.
+///
+/// Filters are nodes of the graph living in the process, with any number of input
+/// and output ports. Unlike a [`Stream`](crate::stream::Stream), a filter processes
+/// the data of all its ports at once, which makes it suited to implement effects.
+pub struct Filter {
+    ptr: ptr::NonNull<pw_sys::pw_filter>,
+    // The filter keeps a pointer on the core
+    _core: Core,
+}
+
+impl Filter {
+    /// Create a [`Filter`]
+    ///
+    /// Initialises a new filter with the given `name` and `properties`.
+    ///
+    /// # Panics
+    /// Will panic if `name` contains a 0 byte.
+    pub fn new(core: &Core, name: &str, properties: Properties) -> Result<Self, Error> {
+        let name = CString::new(name).expect("Invalid byte in filter name");
+        let filter =
+            unsafe { pw_sys::pw_filter_new(core.as_ptr(), name.as_ptr(), properties.into_raw()) };
+        let filter = ptr::NonNull::new(filter).ok_or(Error::CreationFailed)?;
+
+        Ok(Filter {
+            ptr: filter,
+            _core: core.clone(),
+        })
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut pw_sys::pw_filter {
+        self.ptr.as_ptr()
+    }
+
+    // TODO: add non-local version when we'll bind pw_thread_loop_start()
+    #[must_use]
+    pub fn add_listener_local(&self) -> FilterListenerLocalBuilder {
+        FilterListenerLocalBuilder {
+            filter: self,
+            cbs: ListenerLocalCallbacks::default(),
+        }
+    }
+
+    /// Add a port to the filter
+    ///
+    /// Returns the port data, identifying the port in the other port methods such as
+    /// [`Filter::get_dsp_buffer()`].
+    /// It stays valid until the port is removed with [`Filter::remove_port()`] or the
+    /// filter is destroyed.
+    pub fn add_port(
+        &self,
+        direction: spa::Direction,
+        flags: FilterPortFlags,
+        properties: Properties,
+    ) -> Result<*mut c_void, Error> {
+        let port = unsafe {
+            pw_sys::pw_filter_add_port(
+                self.as_ptr(),
+                direction.as_raw(),
+                flags.bits(),
+                0,
+                properties.into_raw(),
+                ptr::null_mut(),
+                0,
+            )
+        };
+
+        if port.is_null() {
+            Err(Error::CreationFailed)
+        } else {
+            Ok(port)
+        }
+    }
+
+    /// Remove a port from the filter
+    ///
+    /// # Safety
+    /// `port_data` must have been returned by [`Filter::add_port()`] on this filter and
+    /// must not be used after this call.
+    pub unsafe fn remove_port(&self, port_data: *mut c_void) -> Result<(), Error> {
+        let r = pw_sys::pw_filter_remove_port(port_data);
+
+        SpaResult::from_c(r).into_sync_result()?;
+        Ok(())
+    }
+
+    /// Connect the filter
+    ///
+    /// `params` are the parameters of the filter node.
+    pub fn connect(&self, flags: FilterFlags, params: &[&Pod]) -> Result<(), Error> {
+        let mut params: Vec<*const spa_sys::spa_pod> =
+            params.iter().map(|p| p.as_raw_ptr() as *const _).collect();
+
+        let r = unsafe {
+            pw_sys::pw_filter_connect(
+                self.as_ptr(),
+                flags.bits(),
+                params.as_mut_ptr(),
+                params.len() as u32,
+            )
+        };
+
+        SpaResult::from_c(r).into_sync_result()?;
+        Ok(())
+    }
+
+    /// Disconnect the filter
+    pub fn disconnect(&self) -> Result<(), Error> {
+        let r = unsafe { pw_sys::pw_filter_disconnect(self.as_ptr()) };
+
+        SpaResult::from_c(r).into_sync_result()?;
+        Ok(())
+    }
+
+    /// Get the DSP buffer of a port
+    ///
+    /// Returns a pointer to the `n_samples` float samples of the port for the current cycle,
+    /// or NULL if there is none.
+    /// This should be called from the `process` callback.
+    ///
+    /// # Safety
+    /// `port_data` must have been returned by [`Filter::add_port()`] for a port using the
+    /// DSP audio format.
+    pub unsafe fn get_dsp_buffer(port_data: *mut c_void, n_samples: u32) -> *mut c_void {
+        pw_sys::pw_filter_get_dsp_buffer(port_data, n_samples)
+    }
+
+    // getters
+
+    /// Get the name of the filter.
+    pub fn name(&self) -> String {
+        let name = unsafe {
+            let name = pw_sys::pw_filter_get_name(self.as_ptr());
+            CStr::from_ptr(name)
+        };
+
+        name.to_string_lossy().to_string()
+    }
+
+    /// Get the node ID of the filter.
+    pub fn node_id(&self) -> u32 {
+        unsafe { pw_sys::pw_filter_get_node_id(self.as_ptr()) }
+    }
+
+    /// Get the current state of the filter.
+    pub fn state(&self) -> FilterState {
+        let mut error: *const c_char = ptr::null();
+        let state = unsafe { pw_sys::pw_filter_get_state(self.as_ptr(), &mut error) };
+        FilterState::from_raw(state, error)
+    }
+}
+
+impl std::fmt::Debug for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Filter")
+            .field("name", &self.name())
+            .field("node-id", &self.node_id())
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+impl Drop for Filter {
+    fn drop(&mut self) {
+        unsafe { pw_sys::pw_filter_destroy(self.as_ptr()) }
+    }
+}
+
+#[derive(Default)]
+struct ListenerLocalCallbacks {
+    state_changed: Option<Box<dyn Fn(FilterState, FilterState)>>,
+    process: Option<Box<dyn Fn(Option<&IoPosition>)>>,
+    command: Option<Box<dyn Fn(&Pod)>>,
+}
+
+pub struct FilterListenerLocalBuilder<'a> {
+    filter: &'a Filter,
+    cbs: ListenerLocalCallbacks,
+}
+
+pub struct FilterListener {
+    // Need to stay allocated while the listener is registered
+    #[allow(dead_code)]
+    events: Pin<Box<pw_sys::pw_filter_events>>,
+    listener: Pin<Box<spa_sys::spa_hook>>,
+    #[allow(dead_code)]
+    data: Box<ListenerLocalCallbacks>,
+}
+
+impl Drop for FilterListener {
+    fn drop(&mut self) {
+        spa::hook::remove(*self.listener);
+    }
+}
+
+impl<'a> FilterListenerLocalBuilder<'a> {
+    /// Set the callback for the `state_changed` event.
+    ///
+    /// Callback parameters: old state, new state.
+    #[must_use]
+    pub fn state_changed<F>(mut self, state_changed: F) -> Self
+    where
+        F: Fn(FilterState, FilterState) + 'static,
+    {
+        self.cbs.state_changed = Some(Box::new(state_changed));
+        self
+    }
+
+    /// Set the callback for the `process` event.
+    ///
+    /// It is called for each cycle of the graph, with the position of the graph if known.
+    /// The data of the ports should be read and written from there,
+    /// using [`Filter::get_dsp_buffer()`] for example.
+    #[must_use]
+    pub fn process<F>(mut self, process: F) -> Self
+    where
+        F: Fn(Option<&IoPosition>) + 'static,
+    {
+        self.cbs.process = Some(Box::new(process));
+        self
+    }
+
+    /// Set the callback for the `command` event.
+    ///
+    /// It is called when the filter receives a command, encoded as a `spa_command` pod.
+    #[must_use]
+    pub fn command<F>(mut self, command: F) -> Self
+    where
+        F: Fn(&Pod) + 'static,
+    {
+        self.cbs.command = Some(Box::new(command));
+        self
+    }
+
+    #[must_use]
+    pub fn register(self) -> FilterListener {
+        unsafe extern "C" fn filter_events_state_changed(
+            data: *mut c_void,
+            old: pw_sys::pw_filter_state,
+            state: pw_sys::pw_filter_state,
+            error: *const c_char,
+        ) {
+            let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
+            let old = FilterState::from_raw(old, error);
+            let state = FilterState::from_raw(state, error);
+            callbacks.state_changed.as_ref().unwrap()(old, state);
+        }
+
+        unsafe extern "C" fn filter_events_process(
+            data: *mut c_void,
+            position: *mut spa_sys::spa_io_position,
+        ) {
+            let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
+            let position = if position.is_null() {
+                None
+            } else {
+                Some(IoPosition::from_raw(position))
+            };
+            callbacks.process.as_ref().unwrap()(position);
+        }
+
+        unsafe extern "C" fn filter_events_command(
+            data: *mut c_void,
+            command: *const spa_sys::spa_command,
+        ) {
+            let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
+            let command = Pod::from_raw(command.cast());
+            callbacks.command.as_ref().unwrap()(command);
+        }
+
+        let e = unsafe {
+            let mut e: Pin<Box<pw_sys::pw_filter_events>> = Box::pin(mem::zeroed());
+            e.version = pw_sys::PW_VERSION_FILTER_EVENTS;
+
+            if self.cbs.state_changed.is_some() {
+                e.state_changed = Some(filter_events_state_changed);
+            }
+            if self.cbs.process.is_some() {
+                e.process = Some(filter_events_process);
+            }
+            if self.cbs.command.is_some() {
+                e.command = Some(filter_events_command);
+            }
+
+            e
+        };
+
+        let (listener, data) = unsafe {
+            let data = Box::into_raw(Box::new(self.cbs));
+            let mut listener: Pin<Box<spa_sys::spa_hook>> = Box::pin(mem::zeroed());
+            let listener_ptr: *mut spa_sys::spa_hook = listener.as_mut().get_unchecked_mut();
+
+            pw_sys::pw_filter_add_listener(
+                self.filter.as_ptr(),
+                listener_ptr,
+                e.as_ref().get_ref(),
+                data as *mut _,
+            );
+
+            (listener, Box::from_raw(data))
+        };
+
+        FilterListener {
+            events: e,
+            listener,
+            data,
+        }
+    }
+}
+
+bitflags! {
+    /// Extra flags that can be used in [`Filter::connect()`]
+    pub struct FilterFlags: pw_sys::pw_filter_flags {
+        /// Start the filter inactive.
+        const INACTIVE = pw_sys::pw_filter_flags_PW_FILTER_FLAG_INACTIVE;
+        /// Be a driver of the graph.
+        const DRIVER = pw_sys::pw_filter_flags_PW_FILTER_FLAG_DRIVER;
+        /// Call `process` from the realtime thread.
+        const RT_PROCESS = pw_sys::pw_filter_flags_PW_FILTER_FLAG_RT_PROCESS;
+        /// Don't call the default latency algorithm but emit the `param_changed` event for the
+        /// ports when `SPA_PARAM_Latency` is changed.
+        const CUSTOM_LATENCY = pw_sys::pw_filter_flags_PW_FILTER_FLAG_CUSTOM_LATENCY;
+    }
+}
+
+bitflags! {
+    /// Extra flags that can be used in [`Filter::add_port()`]
+    pub struct FilterPortFlags: pw_sys::pw_filter_port_flags {
+        /// mmap the buffers except DmaBuf.
+        const MAP_BUFFERS = pw_sys::pw_filter_port_flags_PW_FILTER_PORT_FLAG_MAP_BUFFERS;
+        /// The application will allocate the buffer memory.
+        const ALLOC_BUFFERS = pw_sys::pw_filter_port_flags_PW_FILTER_PORT_FLAG_ALLOC_BUFFERS;
+    }
+}
@@ -122,17 +122,24 @@ pub use spa;
 
 pub mod buffer;
 pub mod channel;
+pub mod client;
 pub mod constants;
 mod context;
 mod core_;
 pub mod data;
+pub mod device;
 mod error;
+pub mod factory;
+pub mod filter;
 pub mod keys;
 pub mod link;
+pub mod log;
 mod loop_;
 mod main_loop;
 pub mod metadata;
+pub mod module;
 pub mod node;
+pub mod permissions;
 pub mod port;
 mod properties;
 pub mod proxy;
@@ -169,6 +176,36 @@ pub unsafe fn deinit() {
     pw_sys::pw_deinit()
 }
 
+/// Get the version of the PipeWire headers this crate was compiled against, such as `0.3.40`.
+pub fn headers_version() -> &'static str {
+    use once_cell::sync::OnceCell;
+    static VERSION: OnceCell<String> = OnceCell::new();
+    VERSION.get_or_init(|| {
+        format!(
+            "{}.{}.{}",
+            pw_sys::PW_MAJOR,
+            pw_sys::PW_MINOR,
+            pw_sys::PW_MICRO
+        )
+    })
+}
+
+/// Get the version of the PipeWire library the application is running with, such as `0.3.40`.
+pub fn library_version() -> &'static str {
+    unsafe {
+        let version = pw_sys::pw_get_library_version();
+        std::ffi::CStr::from_ptr(version).to_str().unwrap()
+    }
+}
+
+/// Check if the version of the PipeWire library the application is running with
+/// is at least `major.minor.micro`.
+///
+/// This can be used to only use features when they are available at runtime.
+pub fn check_version(major: u32, minor: u32, micro: u32) -> bool {
+    unsafe { pw_sys::pw_check_library_version(major as _, minor as _, micro as _) }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,4 +217,20 @@ mod tests {
             deinit();
         }
     }
+
+    #[test]
+    fn version() {
+        assert_eq!(
+            headers_version(),
+            format!(
+                "{}.{}.{}",
+                pw_sys::PW_MAJOR,
+                pw_sys::PW_MINOR,
+                pw_sys::PW_MICRO
+            )
+        );
+        assert!(!library_version().is_empty());
+        assert!(check_version(0, 0, 0));
+        assert!(!check_version(1000, 0, 0));
+    }
 }
@@ -1,4 +1,6 @@
 use std::{
+    borrow::Cow,
+    convert::{TryFrom, TryInto},
     ffi::{c_void, CStr},
     fmt, mem,
     pin::Pin,
@@ -7,6 +9,7 @@ use std::{
 
 use bitflags::bitflags;
 use spa::dict::ForeignDict;
+use spa::pod::Pod;
 use spa::spa_interface_call_method;
 
 use crate::{
@@ -78,6 +81,10 @@ pub struct LinkListenerLocalBuilder<'link> {
 }
 
 impl<'a> LinkListenerLocalBuilder<'a> {
+    /// Add info callback, called when the link info changes.
+    ///
+    /// State transitions, such as from [`LinkState::Negotiating`] to [`LinkState::Active`],
+    /// are notified with [`LinkChangeMask::STATE`] set in [`LinkInfo::change_mask`].
     #[must_use]
     pub fn info<F>(mut self, info: F) -> Self
     where
@@ -170,18 +177,24 @@ impl LinkInfo {
 
     pub fn state(&self) -> LinkState {
         let raw_state = unsafe { self.ptr.as_ref().state };
-        match raw_state {
-            pw_sys::pw_link_state_PW_LINK_STATE_ERROR => {
-                let error = unsafe { CStr::from_ptr(self.ptr.as_ref().error).to_str().unwrap() };
-                LinkState::Error(error)
+        match raw_state.try_into() {
+            Ok(LinkState::Error(_)) => {
+                LinkState::Error(self.error().map(Cow::into_owned).unwrap_or_default())
             }
-            pw_sys::pw_link_state_PW_LINK_STATE_UNLINKED => LinkState::Unlinked,
-            pw_sys::pw_link_state_PW_LINK_STATE_INIT => LinkState::Init,
-            pw_sys::pw_link_state_PW_LINK_STATE_NEGOTIATING => LinkState::Negotiating,
-            pw_sys::pw_link_state_PW_LINK_STATE_ALLOCATING => LinkState::Allocating,
-            pw_sys::pw_link_state_PW_LINK_STATE_PAUSED => LinkState::Paused,
-            pw_sys::pw_link_state_PW_LINK_STATE_ACTIVE => LinkState::Active,
-            _ => panic!("Invalid link state: {}", raw_state),
+            Ok(state) => state,
+            Err(_) => panic!("Invalid link state: {}", raw_state),
+        }
+    }
+
+    /// The error message of the link, if it is in the error state.
+    ///
+    /// Invalid UTF-8 sequences in the message are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn error(&self) -> Option<Cow<'_, str>> {
+        let error = unsafe { self.ptr.as_ref().error };
+        if error.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(error).to_string_lossy() })
         }
     }
 
@@ -190,7 +203,15 @@ impl LinkInfo {
         LinkChangeMask::from_bits(mask).expect("Invalid raw change_mask")
     }
 
-    // TODO: format (requires SPA Pod support before it can be implemented)
+    /// Get the format negotiated on the link, if any.
+    pub fn format(&self) -> Option<&Pod> {
+        let format = unsafe { self.ptr.as_ref().format };
+        if format.is_null() {
+            None
+        } else {
+            Some(unsafe { Pod::from_raw(format) })
+        }
+    }
 
     pub fn props(&self) -> Option<&ForeignDict> {
         self.props.as_ref()
@@ -215,19 +236,63 @@ impl fmt::Debug for LinkInfo {
             .field("input_port_id", &self.input_port_id())
             .field("change-mask", &self.change_mask())
             .field("state", &self.state())
+            .field("error", &self.error())
             .field("props", &self.props())
-            // TODO: .field("format", &self.format())
             .finish()
     }
 }
 
-#[derive(Debug)]
-pub enum LinkState<'a> {
-    Error(&'a str),
+/// The state of a [`Link`], as reported in [`LinkInfo::state`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkState {
+    /// The link is in error, with a description of the error.
+    Error(String),
+    /// The link is unlinked.
     Unlinked,
+    /// The link is initialized.
     Init,
+    /// The link is negotiating formats.
     Negotiating,
+    /// The link is allocating buffers.
     Allocating,
+    /// The link is paused.
     Paused,
+    /// The link is active.
     Active,
 }
+
+impl TryFrom<pw_sys::pw_link_state> for LinkState {
+    type Error = pw_sys::pw_link_state;
+
+    /// Convert a raw `pw_link_state`, returning the raw value back if it is unknown.
+    ///
+    /// As the raw state does not carry the error message, [`LinkState::Error`] is
+    /// returned with an empty string. Use [`LinkInfo::state`] to retrieve it.
+    fn try_from(state: pw_sys::pw_link_state) -> Result<Self, Self::Error> {
+        match state {
+            pw_sys::pw_link_state_PW_LINK_STATE_ERROR => Ok(LinkState::Error(String::new())),
+            pw_sys::pw_link_state_PW_LINK_STATE_UNLINKED => Ok(LinkState::Unlinked),
+            pw_sys::pw_link_state_PW_LINK_STATE_INIT => Ok(LinkState::Init),
+            pw_sys::pw_link_state_PW_LINK_STATE_NEGOTIATING => Ok(LinkState::Negotiating),
+            pw_sys::pw_link_state_PW_LINK_STATE_ALLOCATING => Ok(LinkState::Allocating),
+            pw_sys::pw_link_state_PW_LINK_STATE_PAUSED => Ok(LinkState::Paused),
+            pw_sys::pw_link_state_PW_LINK_STATE_ACTIVE => Ok(LinkState::Active),
+            _ => Err(state),
+        }
+    }
+}
+
+impl fmt::Display for LinkState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkState::Error(error) if error.is_empty() => write!(f, "error"),
+            LinkState::Error(error) => write!(f, "error: {}", error),
+            LinkState::Unlinked => write!(f, "unlinked"),
+            LinkState::Init => write!(f, "init"),
+            LinkState::Negotiating => write!(f, "negotiating"),
+            LinkState::Allocating => write!(f, "allocating"),
+            LinkState::Paused => write!(f, "paused"),
+            LinkState::Active => write!(f, "active"),
+        }
+    }
+}
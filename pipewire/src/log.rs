@@ -0,0 +1,243 @@
+// Copyright The pipewire-rs Contributors.
+// SPDX-License-Identifier: MIT
+
+//! Control of the PipeWire internal logging.
+//!
+//! The initial level is set by [`init`](crate::init) from the `PIPEWIRE_DEBUG` environment variable.
+//! By default, messages are written to `stderr`. They can be redirected with [`set_handler`],
+//! for example to the `log` or `tracing` crates with the handlers enabled by the `log` and `tracing-handler` features.
+
+use std::{
+    ffi::CStr,
+    fmt,
+    os::raw::{c_char, c_int, c_void},
+    ptr,
+    sync::{Once, RwLock},
+};
+
+use once_cell::sync::Lazy;
+
+/// The verbosity of a log message, or of the logging as a whole.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// No message is logged.
+    None,
+    /// Errors.
+    Error,
+    /// Warnings.
+    Warn,
+    /// Informational messages.
+    Info,
+    /// Debug messages.
+    Debug,
+    /// Very verbose messages, such as the ones emitted for each processing cycle.
+    Trace,
+}
+
+impl LogLevel {
+    /// The raw representation of the level.
+    pub fn as_raw(&self) -> spa_sys::spa_log_level {
+        match self {
+            Self::None => spa_sys::SPA_LOG_LEVEL_NONE,
+            Self::Error => spa_sys::SPA_LOG_LEVEL_ERROR,
+            Self::Warn => spa_sys::SPA_LOG_LEVEL_WARN,
+            Self::Info => spa_sys::SPA_LOG_LEVEL_INFO,
+            Self::Debug => spa_sys::SPA_LOG_LEVEL_DEBUG,
+            Self::Trace => spa_sys::SPA_LOG_LEVEL_TRACE,
+        }
+    }
+}
+
+impl From<spa_sys::spa_log_level> for LogLevel {
+    /// Convert a raw `spa_log_level`, levels above `SPA_LOG_LEVEL_TRACE` being mapped to [`LogLevel::Trace`].
+    fn from(raw: spa_sys::spa_log_level) -> Self {
+        match raw {
+            spa_sys::SPA_LOG_LEVEL_NONE => Self::None,
+            spa_sys::SPA_LOG_LEVEL_ERROR => Self::Error,
+            spa_sys::SPA_LOG_LEVEL_WARN => Self::Warn,
+            spa_sys::SPA_LOG_LEVEL_INFO => Self::Info,
+            spa_sys::SPA_LOG_LEVEL_DEBUG => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Error => write!(f, "error"),
+            Self::Warn => write!(f, "warn"),
+            Self::Info => write!(f, "info"),
+            Self::Debug => write!(f, "debug"),
+            Self::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+/// Set the level of the PipeWire internal logging.
+pub fn set_log_level(level: LogLevel) {
+    unsafe { pw_sys::pw_log_set_level(level.as_raw()) }
+}
+
+/// Get the level of the PipeWire internal logging.
+pub fn get_log_level() -> LogLevel {
+    LogLevel::from(unsafe { pw_sys::pw_log_level })
+}
+
+type Handler = dyn Fn(LogLevel, &str, u32, &str, &str) + Send + Sync;
+
+/// The handler installed with [`set_handler`], called by the logger of `pipewire-sys`.
+static HANDLER: Lazy<RwLock<Option<Box<Handler>>>> = Lazy::new(Default::default);
+
+unsafe extern "C" fn call_handler(
+    _data: *mut c_void,
+    level: spa_sys::spa_log_level,
+    file: *const c_char,
+    line: c_int,
+    func: *const c_char,
+    message: *const c_char,
+) {
+    let to_str = |s: *const c_char| {
+        if s.is_null() {
+            "".into()
+        } else {
+            CStr::from_ptr(s).to_string_lossy()
+        }
+    };
+
+    if let Some(handler) = HANDLER.read().unwrap().as_ref() {
+        handler(
+            LogLevel::from(level),
+            &to_str(file),
+            line as u32,
+            &to_str(func),
+            &to_str(message),
+        );
+    }
+}
+
+/// Redirect the PipeWire internal logging to `handler`.
+///
+/// Handler parameters: level, file, line, function, message.
+///
+/// All the messages are forwarded, including the ones logged by SPA plugins.
+/// Messages longer than 4096 bytes are truncated.
+///
+/// The handler may be called from any PipeWire thread, such as the data loop threads.
+/// It replaces the previous handler, which is dropped once no thread is running it anymore.
+pub fn set_handler<F>(handler: F)
+where
+    F: Fn(LogLevel, &str, u32, &str, &str) + Send + Sync + 'static,
+{
+    *HANDLER.write().unwrap() = Some(Box::new(handler));
+
+    // The same logger is used for all the handlers, so it is only created once and never freed,
+    // as other threads may be using it at any time.
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| unsafe {
+        let log = pw_sys::pipewire_rs_log_new(Some(call_handler), ptr::null_mut());
+        assert!(!log.is_null(), "Failed to allocate the logger");
+        pw_sys::pw_log_set(log);
+    });
+}
+
+/// A handler forwarding messages to the `log` crate, with the `pipewire` target.
+///
+/// It can be installed with [`set_handler`].
+#[cfg(feature = "log")]
+pub fn log_handler(level: LogLevel, file: &str, line: u32, _func: &str, message: &str) {
+    let level = match level {
+        LogLevel::None => return,
+        LogLevel::Error => ::log::Level::Error,
+        LogLevel::Warn => ::log::Level::Warn,
+        LogLevel::Info => ::log::Level::Info,
+        LogLevel::Debug => ::log::Level::Debug,
+        LogLevel::Trace => ::log::Level::Trace,
+    };
+
+    ::log::logger().log(
+        &::log::Record::builder()
+            .level(level)
+            .target("pipewire")
+            .file(Some(file))
+            .line(Some(line))
+            .args(format_args!("{}", message))
+            .build(),
+    );
+}
+
+/// A handler forwarding messages to the `tracing` crate as events, with the `pipewire` target.
+///
+/// It can be installed with [`set_handler`].
+#[cfg(feature = "tracing-handler")]
+pub fn tracing_handler(level: LogLevel, file: &str, line: u32, func: &str, message: &str) {
+    use ::tracing_handler as tracing;
+
+    match level {
+        LogLevel::None => {}
+        LogLevel::Error => tracing::error!(target: "pipewire", file, line, func, "{}", message),
+        LogLevel::Warn => tracing::warn!(target: "pipewire", file, line, func, "{}", message),
+        LogLevel::Info => tracing::info!(target: "pipewire", file, line, func, "{}", message),
+        LogLevel::Debug => tracing::debug!(target: "pipewire", file, line, func, "{}", message),
+        LogLevel::Trace => tracing::trace!(target: "pipewire", file, line, func, "{}", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_raw() {
+        assert_eq!(LogLevel::from(spa_sys::SPA_LOG_LEVEL_WARN), LogLevel::Warn);
+        assert_eq!(LogLevel::from(42), LogLevel::Trace);
+        assert_eq!(LogLevel::from(LogLevel::Debug.as_raw()), LogLevel::Debug);
+    }
+
+    #[test]
+    fn set_get() {
+        let level = get_log_level();
+        set_log_level(LogLevel::Info);
+        assert_eq!(get_log_level(), LogLevel::Info);
+        set_log_level(level);
+    }
+
+    #[test]
+    fn handler() {
+        use std::sync::{Arc, Mutex};
+
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let messages_clone = messages.clone();
+        set_handler(move |level, file, line, func, message| {
+            messages_clone.lock().unwrap().push((
+                level,
+                file.to_owned(),
+                line,
+                func.to_owned(),
+                message.to_owned(),
+            ));
+        });
+
+        unsafe {
+            pw_sys::pw_log_log(
+                spa_sys::SPA_LOG_LEVEL_ERROR,
+                b"file.c\0".as_ptr().cast(),
+                42,
+                b"func\0".as_ptr().cast(),
+                b"%s %d\0".as_ptr().cast(),
+                b"foo\0".as_ptr() as *const c_char,
+                7 as c_int,
+            );
+        }
+
+        // Other tests may log concurrently, so only look for our message.
+        assert!(messages.lock().unwrap().contains(&(
+            LogLevel::Error,
+            "file.c".to_owned(),
+            42,
+            "func".to_owned(),
+            "foo 7".to_owned()
+        )));
+    }
+}
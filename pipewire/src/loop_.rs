@@ -1,11 +1,15 @@
 // Copyright The pipewire-rs Contributors.
 // SPDX-License-Identifier: MIT
 
-use std::{convert::TryInto, ops::Deref, os::unix::prelude::*, ptr, time::Duration};
+use std::{
+    collections::HashMap, convert::TryInto, fmt, ops::Deref, os::unix::prelude::*, ptr,
+    sync::Mutex, time::Duration,
+};
 
 use libc::{c_int, c_void};
+use once_cell::sync::Lazy;
 use signal::Signal;
-use spa::{flags::IoFlags, result::SpaResult, spa_interface_call_method};
+use spa::{flags::IoFlags, result::SpaResult, spa_interface_call_method, ReadableDict};
 
 use crate::utils::assert_main_thread;
 
@@ -27,6 +31,19 @@ impl LoopRef {
         &self.0 as *const _ as *mut _
     }
 
+    /// Get the name of the loop, as set with the `loop.name` property.
+    ///
+    /// Only loops created with properties by this crate, such as with [`Loop::with_properties`]
+    /// or [`MainLoop::with_properties`](`crate::MainLoop::with_properties`), have a name.
+    pub fn get_name(&self) -> Option<&str> {
+        let names = LOOP_NAMES.lock().unwrap();
+        let name = names.get(&(self.as_ptr() as usize))?;
+        // Safety: the name is only removed from the map when the loop is destroyed or handed over
+        // with `Loop::into_raw`, which cannot happen while the loop is borrowed.
+        // The string is boxed, so it does not move if the map is resized.
+        Some(unsafe { &*(&**name as *const str) })
+    }
+
     /// Get the file descriptor backing this loop.
     pub fn fd(&self) -> RawFd {
         unsafe {
@@ -40,6 +57,18 @@ impl LoopRef {
         }
     }
 
+    /// Wrap the file descriptor of the loop in the async fd type of an async runtime.
+    ///
+    /// The runtime is selected with the `R` adapter: `TokioAdapter` with the `tokio` feature,
+    /// or `AsyncIoAdapter` with the `async-io` feature.
+    /// Once the async fd is readable, the loop should be iterated with a timeout of zero.
+    ///
+    /// The async fd owns a duplicate of the file descriptor of the loop, so it stays valid
+    /// even if it outlives the loop, but it is then never readable again.
+    pub fn fd_as_async_fd<R: AsyncFdAdapter>(&self) -> std::io::Result<R::AsyncFd> {
+        R::wrap(LoopFd::dup(self.fd())?)
+    }
+
     /// Enter a loop
     ///
     /// Start an iteration of the loop. This function should be called
@@ -362,6 +391,29 @@ impl LoopRef {
     }
 }
 
+/// Property key holding the name of a loop.
+const LOOP_NAME_KEY: &str = "loop.name";
+
+/// Names of the loops created by this crate, indexed by the address of their `pw_loop`.
+///
+/// Not all PipeWire versions store the name in the `pw_loop` itself, so it is kept here instead.
+static LOOP_NAMES: Lazy<Mutex<HashMap<usize, Box<str>>>> = Lazy::new(Default::default);
+
+/// Remember the `loop.name` from `properties` as the name of `loop_`.
+pub(crate) fn register_name<T: ReadableDict>(loop_: *mut pw_sys::pw_loop, properties: &T) {
+    if let Some(name) = properties.get(LOOP_NAME_KEY) {
+        LOOP_NAMES
+            .lock()
+            .unwrap()
+            .insert(loop_ as usize, name.into());
+    }
+}
+
+/// Forget the name of `loop_`, which must be called before it is destroyed.
+pub(crate) fn unregister_name(loop_: *mut pw_sys::pw_loop) {
+    LOOP_NAMES.lock().unwrap().remove(&(loop_ as usize));
+}
+
 pub struct Loop {
     ptr: *mut pw_sys::pw_loop,
 }
@@ -385,14 +437,31 @@ impl Loop {
         unsafe { Self::from_raw(pw_sys::pw_loop_new(std::ptr::null())) }
     }
 
-    // TODO: fn with_props
+    /// Create a new [`Loop`] with the given properties.
+    ///
+    /// The `loop.name` property can be used to give the loop a name.
+    pub fn with_properties<T: ReadableDict>(properties: &T) -> Self {
+        crate::init();
+
+        let l = unsafe { Self::from_raw(pw_sys::pw_loop_new(properties.get_dict_ptr())) };
+        register_name(l.ptr, properties);
+        l
+    }
 
     /// Consume the [`Loop`] and returns the underlying raw [`pw_loop`](`pw_sys::pw_loop`).
     ///
     /// After calling this function, the caller has ownership of the raw [`pw_loop`](`pw_sys::pw_loop`),
     /// and should ensure it is properly destroyed when not used any longer.
     pub fn into_raw(self) -> *mut pw_sys::pw_loop {
-        std::mem::ManuallyDrop::new(self).ptr
+        let l = std::mem::ManuallyDrop::new(self);
+        unregister_name(l.ptr);
+        l.ptr
+    }
+}
+
+impl fmt::Display for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.get_name().unwrap_or("<unnamed loop>"))
     }
 }
 
@@ -418,6 +487,7 @@ impl std::ops::Deref for Loop {
 
 impl std::ops::Drop for Loop {
     fn drop(&mut self) {
+        unregister_name(self.ptr);
         unsafe { pw_sys::pw_loop_destroy(self.ptr) }
     }
 }
@@ -623,6 +693,30 @@ impl<'l> TimerSource<'l> {
 
         SpaResult::from_c(res)
     }
+
+    /// Get the time left before the timer fires next.
+    ///
+    /// Returns `None` if the timer is disarmed, or if its remaining time could not be queried.
+    pub fn remaining(&self) -> Option<Duration> {
+        // The loop implements timers with a timerfd, which is the fd of the source.
+        let fd = unsafe { self.ptr.as_ref().fd };
+        let mut spec: libc::itimerspec = unsafe { std::mem::zeroed() };
+
+        if unsafe { libc::timerfd_gettime(fd, &mut spec) } < 0 {
+            return None;
+        }
+
+        let remaining = Duration::new(
+            spec.it_value.tv_sec.try_into().ok()?,
+            spec.it_value.tv_nsec.try_into().ok()?,
+        );
+
+        if remaining.is_zero() {
+            None
+        } else {
+            Some(remaining)
+        }
+    }
 }
 
 impl<'l> IsSource for TimerSource<'l> {
@@ -636,3 +730,83 @@ impl<'l> Drop for TimerSource<'l> {
         unsafe { self.loop_.destroy_source(self) }
     }
 }
+
+/// A duplicate of the file descriptor of a loop, as returned by [`LoopRef::fd_as_async_fd`].
+///
+/// The duplicate is owned and closed when dropped, independently of the loop.
+#[derive(Debug)]
+pub struct LoopFd(RawFd);
+
+impl LoopFd {
+    fn dup(fd: RawFd) -> std::io::Result<Self> {
+        let fd = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(Self(fd))
+    }
+}
+
+impl AsRawFd for LoopFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+impl Drop for LoopFd {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.0) };
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Trait implemented by the async runtimes supported by [`LoopRef::fd_as_async_fd`].
+///
+/// This trait is sealed and can't be implemented outside of this crate.
+pub trait AsyncFdAdapter: sealed::Sealed {
+    /// The async fd type of the runtime.
+    type AsyncFd;
+
+    #[doc(hidden)]
+    fn wrap(fd: LoopFd) -> std::io::Result<Self::AsyncFd>;
+}
+
+/// [`AsyncFdAdapter`] for `tokio`, creating a [`tokio::io::unix::AsyncFd`].
+///
+/// [`LoopRef::fd_as_async_fd`] must be called from within a tokio runtime.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub enum TokioAdapter {}
+
+#[cfg(feature = "tokio")]
+impl sealed::Sealed for TokioAdapter {}
+
+#[cfg(feature = "tokio")]
+impl AsyncFdAdapter for TokioAdapter {
+    type AsyncFd = tokio::io::unix::AsyncFd<LoopFd>;
+
+    fn wrap(fd: LoopFd) -> std::io::Result<Self::AsyncFd> {
+        tokio::io::unix::AsyncFd::new(fd)
+    }
+}
+
+/// [`AsyncFdAdapter`] for `async-io`, creating an [`async_io::Async`].
+#[cfg(feature = "async-io")]
+#[derive(Debug)]
+pub enum AsyncIoAdapter {}
+
+#[cfg(feature = "async-io")]
+impl sealed::Sealed for AsyncIoAdapter {}
+
+#[cfg(feature = "async-io")]
+impl AsyncFdAdapter for AsyncIoAdapter {
+    type AsyncFd = async_io::Async<LoopFd>;
+
+    fn wrap(fd: LoopFd) -> std::io::Result<Self::AsyncFd> {
+        async_io::Async::new(fd)
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright The pipewire-rs Contributors.
 // SPDX-License-Identifier: MIT
 
+use std::fmt;
 use std::ops::Deref;
 use std::ptr;
 use std::rc::{Rc, Weak};
@@ -37,6 +38,12 @@ impl MainLoop {
     }
 }
 
+impl fmt::Display for MainLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_loop().get_name().unwrap_or("<unnamed loop>"))
+    }
+}
+
 impl Deref for MainLoop {
     type Target = MainLoopInner;
 
@@ -75,7 +82,9 @@ impl MainLoopInner {
             let props = properties.map_or(ptr::null(), |props| props.get_dict_ptr()) as *mut _;
             let l = pw_sys::pw_main_loop_new(props);
             let ptr = ptr::NonNull::new(l).ok_or(Error::CreationFailed)?;
-
+            if let Some(properties) = properties {
+                loop_::register_name(pw_sys::pw_main_loop_get_loop(l), properties);
+            }
             Ok(MainLoopInner { ptr })
         }
     }
@@ -99,6 +108,9 @@ impl MainLoopInner {
 
 impl Drop for MainLoopInner {
     fn drop(&mut self) {
-        unsafe { pw_sys::pw_main_loop_destroy(self.ptr.as_ptr()) }
+        unsafe {
+            loop_::unregister_name(pw_sys::pw_main_loop_get_loop(self.as_ptr()));
+            pw_sys::pw_main_loop_destroy(self.ptr.as_ptr())
+        }
     }
 }
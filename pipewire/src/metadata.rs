@@ -4,23 +4,48 @@
 use std::ffi::CString;
 use std::os::raw::c_char;
 use std::{
+    cell::RefCell,
+    collections::HashMap,
     ffi::{c_void, CStr},
-    mem,
+    fmt, mem,
     pin::Pin,
     ptr,
+    rc::Rc,
 };
 
 use crate::{
     proxy::{Listener, Proxy, ProxyT},
     types::ObjectType,
+    CoreInner, Error,
 };
 use spa::spa_interface_call_method;
 
-#[derive(Debug)]
+type PropertyCache = Rc<RefCell<HashMap<(u32, String), String>>>;
+
+/// A proxy to a metadata object.
+///
+/// To support [`get_property`](Self::get_property), each `Metadata` keeps a copy of all the
+/// properties of the metadata object, updated from a listener registered when the proxy is bound.
+/// The server only sends the existing properties once, right after binding, so the cache cannot
+/// be populated lazily: it always costs a copy of every property and a callback for each change,
+/// even if `get_property` is never called.
 pub struct Metadata {
+    // Keeps `properties` up to date, declared first so it is removed before the proxy is destroyed.
+    #[allow(dead_code)]
+    cache_listener: MetadataListener,
+    properties: PropertyCache,
     proxy: Proxy,
 }
 
+impl fmt::Debug for Metadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metadata")
+            .field("proxy", &self.proxy)
+            .field("properties", &self.properties.borrow())
+            .finish()
+    }
+}
+
 impl ProxyT for Metadata {
     fn type_() -> ObjectType {
         ObjectType::Metadata
@@ -38,14 +63,43 @@ impl ProxyT for Metadata {
     where
         Self: Sized,
     {
-        Self { proxy }
+        let properties = PropertyCache::default();
+        let properties_clone = properties.clone();
+
+        // The server sends all the existing properties once bound, so the listener has to be
+        // registered right away for the cache to be complete.
+        let cache_listener = MetadataListenerLocalBuilder {
+            proxy: &proxy,
+            cbs: ListenerLocalCallbacks::default(),
+        }
+        .property(move |property| {
+            let mut properties = properties_clone.borrow_mut();
+            let subject = property.subject;
+            match (property.key, property.value) {
+                (Some(key), Some(value)) => {
+                    properties.insert((subject, key.to_owned()), value.to_owned());
+                }
+                (Some(key), None) => {
+                    properties.remove(&(subject, key.to_owned()));
+                }
+                (None, _) => properties.retain(|(s, _), _| *s != subject),
+            }
+            0
+        })
+        .register();
+
+        Self {
+            cache_listener,
+            properties,
+            proxy,
+        }
     }
 }
 
 impl Metadata {
     pub fn add_listener_local(&self) -> MetadataListenerLocalBuilder {
         MetadataListenerLocalBuilder {
-            metadata: self,
+            proxy: &self.proxy,
             cbs: ListenerLocalCallbacks::default(),
         }
     }
@@ -68,6 +122,32 @@ impl Metadata {
         }
     }
 
+    /// Get the value of the `key` property of `subject`, or `None` if it is not set.
+    ///
+    /// The value is read from the copy of the properties kept by the proxy, see [`Metadata`].
+    /// Metadata properties are only announced through events, so this performs a
+    /// [`CoreInner::roundtrip`] on the core of the metadata to make sure all the pending
+    /// property changes have been received before looking up the value.
+    ///
+    /// This is a blocking call iterating the main loop of the core, it must not be used from
+    /// the loop thread, such as from a callback.
+    ///
+    /// # Errors
+    /// Returns the error of the roundtrip if it fails, such as when the connection is lost.
+    pub fn get_property(&self, subject: u32, key: &str) -> Result<Option<String>, Error> {
+        let core = unsafe {
+            let core = pw_sys::pw_proxy_get_core(self.proxy.as_ptr());
+            CoreInner::from_ptr(ptr::NonNull::new(core).expect("proxy has no core"))
+        };
+        core.roundtrip()?;
+
+        Ok(self
+            .properties
+            .borrow()
+            .get(&(subject, key.to_owned()))
+            .cloned())
+    }
+
     pub fn clear(&self) {
         unsafe {
             spa::spa_interface_call_method!(
@@ -96,28 +176,42 @@ impl<'meta> Drop for MetadataListener {
     }
 }
 
+/// A metadata property, as received by the
+/// [`property`](MetadataListenerLocalBuilder::property) callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MetadataProperty<'a> {
+    /// The id of the object the property applies to.
+    pub subject: u32,
+    /// The key of the property, `None` if all the properties of `subject` were removed.
+    pub key: Option<&'a str>,
+    /// The type of the value, such as `Spa:String:JSON`.
+    pub type_: Option<&'a str>,
+    /// The value of the property, `None` if the property was removed.
+    pub value: Option<&'a str>,
+}
+
 #[derive(Default)]
 struct ListenerLocalCallbacks {
-    #[allow(clippy::type_complexity)]
-    property: Option<Box<dyn Fn(u32, Option<&str>, Option<&str>, Option<&str>) -> i32>>,
+    property: Option<Box<dyn Fn(&MetadataProperty<'_>) -> i32>>,
 }
 
 #[must_use]
 pub struct MetadataListenerLocalBuilder<'meta> {
-    metadata: &'meta Metadata,
+    proxy: &'meta Proxy,
     cbs: ListenerLocalCallbacks,
 }
 
 impl<'meta> MetadataListenerLocalBuilder<'meta> {
     /// Add property changed callback.
     ///
-    /// Callback parameters: subject, key, type, value.
+    /// The callback receives the changed [`MetadataProperty`].
     ///
     /// `None` for `value` means removal of property.
     /// `None` for `key` means removal of all properties.
     pub fn property<F>(mut self, property: F) -> Self
     where
-        F: Fn(u32, Option<&str>, Option<&str>, Option<&str>) -> i32 + 'static,
+        F: Fn(&MetadataProperty<'_>) -> i32 + 'static,
     {
         self.cbs.property = Some(Box::new(property));
         self
@@ -148,12 +242,12 @@ impl<'meta> MetadataListenerLocalBuilder<'meta> {
             } else {
                 None
             };
-            callbacks.property.as_ref().unwrap()(
+            callbacks.property.as_ref().unwrap()(&MetadataProperty {
                 subject,
-                key.as_deref(),
-                type_.as_deref(),
-                value.as_deref(),
-            )
+                key: key.as_deref(),
+                type_: type_.as_deref(),
+                value: value.as_deref(),
+            })
         }
 
         let e = unsafe {
@@ -168,7 +262,7 @@ impl<'meta> MetadataListenerLocalBuilder<'meta> {
         };
 
         let (listener, data) = unsafe {
-            let metadata = &self.metadata.proxy.as_ptr();
+            let metadata = &self.proxy.as_ptr();
 
             let data = Box::into_raw(Box::new(self.cbs));
             let mut listener: Pin<Box<spa_sys::spa_hook>> = Box::pin(mem::zeroed());
@@ -4,7 +4,12 @@
 use bitflags::bitflags;
 use libc::c_void;
 use std::pin::Pin;
-use std::{ffi::CStr, ptr};
+use std::{
+    borrow::Cow,
+    convert::{TryFrom, TryInto},
+    ffi::CStr,
+    ptr,
+};
 use std::{fmt, mem};
 
 use crate::{
@@ -12,6 +17,8 @@ use crate::{
     types::ObjectType,
 };
 use spa::dict::ForeignDict;
+use spa::param::{latency::LatencyInfo, ParamType};
+use spa::pod::{builder::PodBuilder, Pod};
 use spa::spa_interface_call_method;
 
 #[derive(Debug)]
@@ -49,13 +56,129 @@ impl Node {
             cbs: ListenerLocalCallbacks::default(),
         }
     }
+
+    /// Subscribe to parameter changes
+    ///
+    /// Automatically emit `param` events for the given ids when they are changed
+    pub fn subscribe_params(&self, ids: &[ParamType]) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_node_methods,
+                subscribe_params,
+                ids.as_ptr() as *mut _,
+                ids.len().try_into().unwrap()
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Enumerate node parameters
+    ///
+    /// Start enumeration of node parameters. For each param, a `param` event will be emitted.
+    ///
+    /// # Parameters
+    /// - `seq`: a sequence number to place in the reply
+    /// - `id`: the parameter id to enum, or `SPA_PARAM_List` for all
+    /// - `index`: the index of the first param to return
+    /// - `num`: the maximum number of params to return
+    /// - `filter`: a param filter, or `None`
+    pub fn enum_params(
+        &self,
+        seq: i32,
+        id: ParamType,
+        index: u32,
+        num: u32,
+        filter: Option<&Pod>,
+    ) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_node_methods,
+                enum_params,
+                seq,
+                id.as_raw(),
+                index,
+                num,
+                filter.map_or(ptr::null(), |f| f.as_raw_ptr())
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Set a parameter on the node
+    ///
+    /// # Parameters
+    /// - `id`: the parameter id to set
+    /// - `flags`: extra parameter flags
+    /// - `param`: the parameter to set
+    pub fn set_param(&self, id: ParamType, flags: u32, param: &Pod) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_node_methods,
+                set_param,
+                id.as_raw(),
+                flags,
+                param.as_raw_ptr()
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Send a command to the node.
+    ///
+    /// `command` must be a pod encoding a `spa_command_node`, such as a `Suspend`, `Pause` or `Start` command.
+    ///
+    /// Commands are usually only honoured when sent by a client with enough permissions on the node,
+    /// such as the session manager.
+    pub fn send_command(&self, command: &Pod) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_node_methods,
+                send_command,
+                command.as_raw_ptr().cast()
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Send a command without arguments, such as `SPA_NODE_COMMAND_Pause`, to the node.
+    fn send_node_command(&self, id: u32) -> spa::SpaResult {
+        let mut builder = PodBuilder::new();
+        let frame = builder.begin_object(spa_sys::SPA_TYPE_COMMAND_Node, id);
+        builder.end_object(frame);
+
+        self.send_command(&builder.build_owned())
+    }
+
+    /// Send a `Pause` command to the node.
+    pub fn pause(&self) -> spa::SpaResult {
+        self.send_node_command(spa_sys::SPA_NODE_COMMAND_Pause)
+    }
+
+    /// Send a `Start` command to the node.
+    pub fn start(&self) -> spa::SpaResult {
+        self.send_node_command(spa_sys::SPA_NODE_COMMAND_Start)
+    }
+
+    /// Send a `Flush` command to the node.
+    pub fn flush(&self) -> spa::SpaResult {
+        self.send_node_command(spa_sys::SPA_NODE_COMMAND_Flush)
+    }
 }
 
 #[derive(Default)]
 struct ListenerLocalCallbacks {
     info: Option<Box<dyn Fn(&NodeInfo)>>,
     #[allow(clippy::type_complexity)]
-    param: Option<Box<dyn Fn(i32, u32, u32, u32)>>, // TODO: add params
+    param: Option<Box<dyn Fn(i32, ParamType, u32, u32, Option<&Pod>)>>,
+    latency: Option<Box<dyn Fn(&LatencyInfo)>>,
 }
 
 pub struct NodeListenerLocalBuilder<'a> {
@@ -102,20 +225,25 @@ impl NodeInfo {
     }
 
     pub fn state(&self) -> NodeState {
-        let state = unsafe { self.ptr.as_ref().state };
-        match state {
-            pw_sys::pw_node_state_PW_NODE_STATE_ERROR => {
-                let error = unsafe {
-                    let error = self.ptr.as_ref().error;
-                    CStr::from_ptr(error).to_str().unwrap()
-                };
-                NodeState::Error(error)
+        let raw_state = unsafe { self.ptr.as_ref().state };
+        match raw_state.try_into() {
+            Ok(NodeState::Error(_)) => {
+                NodeState::Error(self.error().map(Cow::into_owned).unwrap_or_default())
             }
-            pw_sys::pw_node_state_PW_NODE_STATE_CREATING => NodeState::Creating,
-            pw_sys::pw_node_state_PW_NODE_STATE_SUSPENDED => NodeState::Suspended,
-            pw_sys::pw_node_state_PW_NODE_STATE_IDLE => NodeState::Idle,
-            pw_sys::pw_node_state_PW_NODE_STATE_RUNNING => NodeState::Running,
-            _ => panic!("Invalid node state: {}", state),
+            Ok(state) => state,
+            Err(_) => panic!("Invalid node state: {}", raw_state),
+        }
+    }
+
+    /// The error message of the node, if it is in the error state.
+    ///
+    /// Invalid UTF-8 sequences in the message are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn error(&self) -> Option<Cow<'_, str>> {
+        let error = unsafe { self.ptr.as_ref().error };
+        if error.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(error).to_string_lossy() })
         }
     }
 
@@ -135,15 +263,53 @@ bitflags! {
     }
 }
 
-#[derive(Debug)]
-pub enum NodeState<'a> {
-    Error(&'a str),
+/// The state of a [`Node`], as reported in [`NodeInfo::state`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeState {
+    /// The node is in error, with a description of the error.
+    Error(String),
+    /// The node is being created.
     Creating,
+    /// The node is suspended, the device might be closed.
     Suspended,
+    /// The node is running but there is no active port.
     Idle,
+    /// The node is running.
     Running,
 }
 
+impl TryFrom<pw_sys::pw_node_state> for NodeState {
+    type Error = pw_sys::pw_node_state;
+
+    /// Convert a raw `pw_node_state`, returning the raw value back if it is unknown.
+    ///
+    /// As the raw state does not carry the error message, [`NodeState::Error`] is
+    /// returned with an empty string. Use [`NodeInfo::state`] to retrieve it.
+    fn try_from(state: pw_sys::pw_node_state) -> Result<Self, Self::Error> {
+        match state {
+            pw_sys::pw_node_state_PW_NODE_STATE_ERROR => Ok(NodeState::Error(String::new())),
+            pw_sys::pw_node_state_PW_NODE_STATE_CREATING => Ok(NodeState::Creating),
+            pw_sys::pw_node_state_PW_NODE_STATE_SUSPENDED => Ok(NodeState::Suspended),
+            pw_sys::pw_node_state_PW_NODE_STATE_IDLE => Ok(NodeState::Idle),
+            pw_sys::pw_node_state_PW_NODE_STATE_RUNNING => Ok(NodeState::Running),
+            _ => Err(state),
+        }
+    }
+}
+
+impl fmt::Display for NodeState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeState::Error(error) if error.is_empty() => write!(f, "error"),
+            NodeState::Error(error) => write!(f, "error: {}", error),
+            NodeState::Creating => write!(f, "creating"),
+            NodeState::Suspended => write!(f, "suspended"),
+            NodeState::Idle => write!(f, "idle"),
+            NodeState::Running => write!(f, "running"),
+        }
+    }
+}
+
 impl fmt::Debug for NodeInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("NodeInfo")
@@ -154,6 +320,7 @@ impl fmt::Debug for NodeInfo {
             .field("n-input-ports", &self.n_input_ports())
             .field("n-output-ports", &self.n_output_ports())
             .field("state", &self.state())
+            .field("error", &self.error())
             .field("props", &self.props())
             .finish()
     }
@@ -177,6 +344,7 @@ impl<'a> Drop for NodeListener {
 }
 
 impl<'a> NodeListenerLocalBuilder<'a> {
+    /// Add info callback, called when the node info changes.
     #[must_use]
     pub fn info<F>(mut self, info: F) -> Self
     where
@@ -186,15 +354,35 @@ impl<'a> NodeListenerLocalBuilder<'a> {
         self
     }
 
+    /// Add param callback.
+    ///
+    /// Callback parameters: seq, id, index, next, param.
+    ///
+    /// `param` may be `None` if the node did not provide a param,
+    /// for example once all the params requested by [`Node::enum_params`] have been emitted.
     #[must_use]
     pub fn param<F>(mut self, param: F) -> Self
     where
-        F: Fn(i32, u32, u32, u32) + 'static,
+        F: Fn(i32, ParamType, u32, u32, Option<&Pod>) + 'static,
     {
         self.cbs.param = Some(Box::new(param));
         self
     }
 
+    /// Add latency callback.
+    ///
+    /// Called with the parsed latency of each `SPA_PARAM_Latency` param emitted by the node,
+    /// such as after subscribing to them with [`Node::subscribe_params`].
+    /// Params that cannot be parsed are ignored.
+    #[must_use]
+    pub fn latency<F>(mut self, latency: F) -> Self
+    where
+        F: Fn(&LatencyInfo) + 'static,
+    {
+        self.cbs.latency = Some(Box::new(latency));
+        self
+    }
+
     #[must_use]
     pub fn register(self) -> NodeListener {
         unsafe extern "C" fn node_events_info(
@@ -213,10 +401,25 @@ impl<'a> NodeListenerLocalBuilder<'a> {
             id: u32,
             index: u32,
             next: u32,
-            _param: *const spa_sys::spa_pod,
+            param: *const spa_sys::spa_pod,
         ) {
             let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
-            callbacks.param.as_ref().unwrap()(seq, id, index, next);
+            let param = if !param.is_null() {
+                Some(Pod::from_raw(param))
+            } else {
+                None
+            };
+            let id = ParamType::from_raw(id);
+            if let Some(cb) = callbacks.param.as_ref() {
+                cb(seq, id, index, next, param);
+            }
+            if let (Some(cb), Some(param)) = (callbacks.latency.as_ref(), param) {
+                if id == ParamType::LATENCY {
+                    if let Ok(info) = LatencyInfo::from_pod(param) {
+                        cb(&info);
+                    }
+                }
+            }
         }
 
         let e = unsafe {
@@ -226,7 +429,7 @@ impl<'a> NodeListenerLocalBuilder<'a> {
             if self.cbs.info.is_some() {
                 e.info = Some(node_events_info);
             }
-            if self.cbs.param.is_some() {
+            if self.cbs.param.is_some() || self.cbs.latency.is_some() {
                 e.param = Some(node_events_param);
             }
 
@@ -0,0 +1,75 @@
+// Copyright The pipewire-rs Contributors.
+// SPDX-License-Identifier: MIT
+
+use bitflags::bitflags;
+
+bitflags! {
+    pub struct PermissionFlags: u32 {
+        const R = pw_sys::PW_PERM_R;
+        const W = pw_sys::PW_PERM_W;
+        const X = pw_sys::PW_PERM_X;
+        const M = pw_sys::PW_PERM_M;
+    }
+}
+
+impl PermissionFlags {
+    /// Returns an empty set of permissions, granting nothing.
+    ///
+    /// This is equivalent to [`PermissionFlags::empty`].
+    pub const fn none() -> Self {
+        Self::empty()
+    }
+}
+
+/// The permissions a client has on a global object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permission {
+    /// The id of the global object, or `PW_ID_ANY` for the default permissions.
+    pub id: u32,
+    /// The permissions granted on the object.
+    pub flags: PermissionFlags,
+}
+
+impl Permission {
+    pub fn new(id: u32, flags: PermissionFlags) -> Self {
+        Self { id, flags }
+    }
+
+    /// Create a `Permission` from a raw `pw_permission`.
+    ///
+    /// Unknown permission bits are ignored.
+    pub fn from_raw(raw: pw_sys::pw_permission) -> Self {
+        Self {
+            id: raw.id,
+            flags: PermissionFlags::from_bits_truncate(raw.permissions),
+        }
+    }
+
+    /// The raw representation of the permission.
+    pub fn as_raw(&self) -> pw_sys::pw_permission {
+        pw_sys::pw_permission {
+            id: self.id,
+            permissions: self.flags.bits(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw() {
+        let permission = Permission::new(42, PermissionFlags::R | PermissionFlags::X);
+        let raw = permission.as_raw();
+        assert_eq!(raw.id, 42);
+        assert_eq!(raw.permissions, pw_sys::PW_PERM_R | pw_sys::PW_PERM_X);
+        assert_eq!(Permission::from_raw(raw), permission);
+    }
+
+    #[test]
+    fn none() {
+        assert!(PermissionFlags::none().is_empty());
+        assert!(PermissionFlags::all().contains(PermissionFlags::R | PermissionFlags::W));
+    }
+}
@@ -3,7 +3,7 @@
 
 use bitflags::bitflags;
 use libc::c_void;
-use std::{fmt, mem};
+use std::{convert::TryInto, fmt, mem};
 use std::{pin::Pin, ptr};
 
 use crate::{
@@ -12,6 +12,8 @@ use crate::{
     types::ObjectType,
 };
 use spa::dict::ForeignDict;
+use spa::param::ParamType;
+use spa::pod::Pod;
 use spa::spa_interface_call_method;
 
 #[derive(Debug)]
@@ -49,13 +51,64 @@ impl Port {
             cbs: ListenerLocalCallbacks::default(),
         }
     }
+
+    /// Subscribe to parameter changes
+    ///
+    /// Automatically emit `param` events for the given ids when they are changed
+    pub fn subscribe_params(&self, ids: &[ParamType]) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_port_methods,
+                subscribe_params,
+                ids.as_ptr() as *mut _,
+                ids.len().try_into().unwrap()
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
+
+    /// Enumerate port parameters
+    ///
+    /// Start enumeration of port parameters. For each param, a `param` event will be emitted.
+    ///
+    /// # Parameters
+    /// - `seq`: a sequence number to place in the reply
+    /// - `id`: the parameter id to enum, or `SPA_PARAM_List` for all
+    /// - `index`: the index of the first param to return
+    /// - `num`: the maximum number of params to return
+    /// - `filter`: a param filter, or `None`
+    pub fn enum_params(
+        &self,
+        seq: i32,
+        id: ParamType,
+        index: u32,
+        num: u32,
+        filter: Option<&Pod>,
+    ) -> spa::SpaResult {
+        let res = unsafe {
+            spa_interface_call_method!(
+                self.proxy.as_ptr(),
+                pw_sys::pw_port_methods,
+                enum_params,
+                seq,
+                id.as_raw(),
+                index,
+                num,
+                filter.map_or(ptr::null(), |f| f.as_raw_ptr())
+            )
+        };
+
+        spa::SpaResult::from_c(res)
+    }
 }
 
 #[derive(Default)]
 struct ListenerLocalCallbacks {
     info: Option<Box<dyn Fn(&PortInfo)>>,
     #[allow(clippy::type_complexity)]
-    param: Option<Box<dyn Fn(i32, u32, u32, u32)>>, // TODO: add params
+    param: Option<Box<dyn Fn(i32, ParamType, u32, u32, Option<&Pod>)>>,
 }
 
 pub struct PortListenerLocalBuilder<'a> {
@@ -94,7 +147,11 @@ impl PortInfo {
     pub fn props(&self) -> Option<&ForeignDict> {
         self.props.as_ref()
     }
-    // TODO: params
+
+    /// Get the number of params the port has.
+    pub fn n_params(&self) -> u32 {
+        unsafe { self.ptr.as_ref().n_params }
+    }
 }
 
 bitflags! {
@@ -110,6 +167,7 @@ impl fmt::Debug for PortInfo {
             .field("id", &self.id())
             .field("direction", &self.direction())
             .field("change-mask", &self.change_mask())
+            .field("n-params", &self.n_params())
             .field("props", &self.props())
             .finish()
     }
@@ -133,6 +191,7 @@ impl<'a> Drop for PortListener {
 }
 
 impl<'a> PortListenerLocalBuilder<'a> {
+    /// Add info callback, called when the port info changes.
     #[must_use]
     pub fn info<F>(mut self, info: F) -> Self
     where
@@ -142,10 +201,16 @@ impl<'a> PortListenerLocalBuilder<'a> {
         self
     }
 
+    /// Add param callback.
+    ///
+    /// Callback parameters: seq, id, index, next, param.
+    ///
+    /// `param` may be `None` if the port did not provide a param,
+    /// for example once all the params requested by [`Port::enum_params`] have been emitted.
     #[must_use]
     pub fn param<F>(mut self, param: F) -> Self
     where
-        F: Fn(i32, u32, u32, u32) + 'static,
+        F: Fn(i32, ParamType, u32, u32, Option<&Pod>) + 'static,
     {
         self.cbs.param = Some(Box::new(param));
         self
@@ -169,10 +234,15 @@ impl<'a> PortListenerLocalBuilder<'a> {
             id: u32,
             index: u32,
             next: u32,
-            _param: *const spa_sys::spa_pod,
+            param: *const spa_sys::spa_pod,
         ) {
             let callbacks = (data as *mut ListenerLocalCallbacks).as_ref().unwrap();
-            callbacks.param.as_ref().unwrap()(seq, id, index, next);
+            let param = if !param.is_null() {
+                Some(Pod::from_raw(param))
+            } else {
+                None
+            };
+            callbacks.param.as_ref().unwrap()(seq, ParamType::from_raw(id), index, next, param);
         }
 
         let e = unsafe {
@@ -112,6 +112,12 @@ impl Properties {
     }
 }
 
+impl From<&spa::dict::SpaDict> for Properties {
+    fn from(dict: &spa::dict::SpaDict) -> Self {
+        Self::from_dict(dict)
+    }
+}
+
 impl ReadableDict for Properties {
     fn get_dict_ptr(&self) -> *const spa_sys::spa_dict {
         self.as_ptr().cast()
@@ -23,6 +23,9 @@ impl Proxy {
         self.ptr.as_ptr()
     }
 
+    /// Add a listener for the lifecycle events of the proxy.
+    ///
+    /// The returned [`ProxyListener`] must be kept alive for the callbacks to be called.
     pub fn add_listener_local(&self) -> ProxyListenerLocalBuilder {
         ProxyListenerLocalBuilder {
             proxy: self,
@@ -30,10 +33,32 @@ impl Proxy {
         }
     }
 
+    /// Get the local id of the proxy, used to identify it in the protocol.
     pub fn id(&self) -> u32 {
         unsafe { pw_sys::pw_proxy_get_id(self.as_ptr()) }
     }
 
+    /// Get the id of the global the proxy is bound to.
+    ///
+    /// This is the id of the object in the registry, which differs from [`id`](Self::id).
+    /// Returns `None` if the proxy is not bound yet.
+    pub fn bound_id(&self) -> Option<u32> {
+        let id = unsafe { pw_sys::pw_proxy_get_bound_id(self.as_ptr()) };
+        if id == spa_sys::SPA_ID_INVALID {
+            None
+        } else {
+            Some(id)
+        }
+    }
+
+    /// Get the version of the interface of the proxy.
+    ///
+    /// This is the version negotiated when binding the object, so it can be used to check
+    /// whether methods and events introduced in later versions of the interface are available.
+    pub fn version(&self) -> u32 {
+        self.get_type().1
+    }
+
     /// Get the type of the proxy as well as it's version.
     pub fn get_type(&self) -> (ObjectType, u32) {
         unsafe {
@@ -141,6 +166,7 @@ pub struct ProxyListenerLocalBuilder<'a> {
 }
 
 impl<'a> ProxyListenerLocalBuilder<'a> {
+    /// Add destroy callback, called when the proxy is destroyed.
     #[must_use]
     pub fn destroy<F>(mut self, destroy: F) -> Self
     where
@@ -150,6 +176,9 @@ impl<'a> ProxyListenerLocalBuilder<'a> {
         self
     }
 
+    /// Add bound callback, called when the proxy is bound to a global.
+    ///
+    /// Callback parameters: the id of the global the proxy is bound to.
     #[must_use]
     pub fn bound<F>(mut self, bound: F) -> Self
     where
@@ -159,6 +188,9 @@ impl<'a> ProxyListenerLocalBuilder<'a> {
         self
     }
 
+    /// Add removed callback, called when the object of the proxy was removed on the server.
+    ///
+    /// The proxy should then be dropped.
     #[must_use]
     pub fn removed<F>(mut self, removed: F) -> Self
     where
@@ -168,6 +200,7 @@ impl<'a> ProxyListenerLocalBuilder<'a> {
         self
     }
 
+    /// Add done callback, called with the sequence number of a completed `sync` on the proxy.
     #[must_use]
     pub fn done<F>(mut self, done: F) -> Self
     where
@@ -177,6 +210,13 @@ impl<'a> ProxyListenerLocalBuilder<'a> {
         self
     }
 
+    /// Add error callback, called when the server reports an error for the proxy.
+    ///
+    /// Callback parameters: seq, res, message.
+    ///
+    /// - `seq`: the sequence number of the request that caused the error
+    /// - `res`: a negative errno style error code
+    /// - `message`: a description of the error
     #[must_use]
     pub fn error<F>(mut self, error: F) -> Self
     where
@@ -6,6 +6,7 @@
 use crate::buffer::Buffer;
 use crate::{error::Error, Core, IsLoop, MainLoop, Properties, PropertiesRef};
 use bitflags::bitflags;
+use spa::pod::Pod;
 use spa::result::SpaResult;
 use std::fmt::Debug;
 use std::{
@@ -44,6 +45,55 @@ impl StreamState {
     }
 }
 
+/// Timing information of a [`Stream`], as returned by [`Stream::get_time()`].
+#[derive(Debug, Clone, Copy)]
+pub struct StreamTime {
+    /// The time in nanoseconds, in the `CLOCK_MONOTONIC` clock, when the time was sampled.
+    pub now: i64,
+    /// The duration of one tick, in seconds.
+    pub rate: spa::utils::Fraction,
+    /// The position of the stream, in ticks.
+    pub ticks: u64,
+    /// The delay to the device, in ticks.
+    pub delay: i64,
+    /// The amount of data queued in the stream, in bytes or samples.
+    pub queued: u64,
+}
+
+impl StreamTime {
+    fn from_raw(time: &pw_sys::pw_time) -> Self {
+        Self {
+            now: time.now,
+            rate: time.rate.into(),
+            ticks: time.ticks,
+            delay: time.delay,
+            queued: time.queued,
+        }
+    }
+
+    /// Convert a number of `ticks` to nanoseconds, using the rate of the stream.
+    ///
+    /// Returns 0 if the rate is not known yet.
+    pub fn ticks_to_nsec(&self, ticks: i64) -> i64 {
+        if self.rate.denom == 0 {
+            return 0;
+        }
+
+        (i128::from(ticks) * 1_000_000_000 * i128::from(self.rate.num)
+            / i128::from(self.rate.denom)) as i64
+    }
+
+    /// The position of the stream, in nanoseconds.
+    pub fn position_nsec(&self) -> i64 {
+        self.ticks_to_nsec(self.ticks as i64)
+    }
+
+    /// The delay to the device, in nanoseconds.
+    pub fn delay_nsec(&self) -> i64 {
+        self.ticks_to_nsec(self.delay)
+    }
+}
+
 /// A wrapper around the pipewire stream interface. Streams are a higher
 /// level abstraction around nodes in the graph. A stream can be used to send or
 /// receive frames of audio of video data by connecting it to another node.
@@ -153,14 +203,19 @@ impl<D> Stream<D> {
     ///
     /// Tries to connect to the node `id` in the given `direction`. If no node
     /// is provided then any suitable node will be used.
-    // FIXME: high-level API for params
+    ///
+    /// `params` are the format parameters the stream supports, as built with
+    /// [`spa::pod::builder::PodBuilder`] for example.
     pub fn connect(
         &self,
         direction: spa::Direction,
         id: Option<u32>,
         flags: StreamFlags,
-        params: &mut [*const spa_sys::spa_pod],
+        params: &[&Pod],
     ) -> Result<(), Error> {
+        let mut params: Vec<*const spa_sys::spa_pod> =
+            params.iter().map(|p| p.as_raw_ptr() as *const _).collect();
+
         let r = unsafe {
             pw_sys::pw_stream_connect(
                 self.as_ptr(),
@@ -180,8 +235,14 @@ impl<D> Stream<D> {
     ///
     /// Call from the `param_changed` callback to negotiate a new set of
     /// parameters for the stream.
-    // FIXME: high-level API for params
-    pub fn update_params(&self, params: &mut [*const spa_sys::spa_pod]) -> Result<(), Error> {
+    ///
+    /// Once the format has been chosen in the `param_changed` callback, this **must** be
+    /// called with the parameters depending on it, such as the buffer requirements, to
+    /// complete the negotiation. Otherwise the stream will never start streaming.
+    pub fn update_params(&self, params: &[&Pod]) -> Result<(), Error> {
+        let mut params: Vec<*const spa_sys::spa_pod> =
+            params.iter().map(|p| p.as_raw_ptr() as *const _).collect();
+
         let r = unsafe {
             pw_sys::pw_stream_update_params(self.as_ptr(), params.as_mut_ptr(), params.len() as u32)
         };
@@ -191,6 +252,13 @@ impl<D> Stream<D> {
     }
 
     /// Activate or deactivate the stream
+    ///
+    /// Deactivating a connected stream pauses it without disconnecting it: the stream goes
+    /// from [`StreamState::Streaming`] to [`StreamState::Paused`] and its node stays registered.
+    /// Activating it again resumes the processing.
+    ///
+    /// This must be called from the thread running the loop of the stream, or with the
+    /// thread loop locked.
     pub fn set_active(&self, active: bool) -> Result<(), Error> {
         let r = unsafe { pw_sys::pw_stream_set_active(self.as_ptr(), active) };
 
@@ -254,8 +322,13 @@ impl<D> Stream<D> {
         }
     }
 
-    /// Flush the stream. When  `drain` is `true`, the `drained` callback will
-    /// be called when all data is played or recorded.
+    /// Flush the stream, discarding the queued buffers.
+    ///
+    /// When `drain` is `true`, the queued data is played instead and the `drained`
+    /// callback will be called once all of it has been played. No more data should be
+    /// produced before that.
+    ///
+    /// Draining is only valid for playback streams, not for capture streams.
     pub fn flush(&self, drain: bool) -> Result<(), Error> {
         let r = unsafe { pw_sys::pw_stream_flush(self.as_ptr(), drain) };
 
@@ -263,6 +336,21 @@ impl<D> Stream<D> {
         Ok(())
     }
 
+    /// Trigger a process cycle of the graph the stream is driving.
+    ///
+    /// This is only meaningful for streams connected with [`StreamFlags::DRIVER`], where the
+    /// application is the clock source of the graph. It tells the graph that data is ready,
+    /// which will start a new cycle and call the `process` callback of the stream.
+    ///
+    /// Unlike [`Stream::set_active()`], which controls whether the stream processes data at
+    /// all, this schedules a single processing cycle.
+    pub fn trigger_process(&self) -> Result<(), Error> {
+        let r = unsafe { pw_sys::pw_stream_trigger_process(self.as_ptr()) };
+
+        SpaResult::from_c(r).into_sync_result()?;
+        Ok(())
+    }
+
     // TODO: pw_stream_set_control()
 
     // getters
@@ -299,8 +387,20 @@ impl<D> Stream<D> {
         unsafe { pw_sys::pw_stream_get_node_id(self.as_ptr()) }
     }
 
+    /// Get the timing information of the stream.
+    ///
+    /// The returned time is relative to the clock of the graph the stream is scheduled in.
+    pub fn get_time(&self) -> Result<StreamTime, Error> {
+        let mut time: pw_sys::pw_time = unsafe { mem::zeroed() };
+        // pw_stream_get_time_n() is not available in all the supported PipeWire versions,
+        // and all the fields read by StreamTime are filled in by pw_stream_get_time().
+        let r = unsafe { pw_sys::pw_stream_get_time(self.as_ptr(), &mut time) };
+
+        SpaResult::from_c(r).into_sync_result()?;
+        Ok(StreamTime::from_raw(&time))
+    }
+
     // TODO: pw_stream_get_core()
-    // TODO: pw_stream_get_time()
 }
 
 impl<D: Default> Stream<D> {
@@ -368,7 +468,7 @@ impl<D> std::fmt::Debug for Stream<D> {
     }
 }
 
-type ParamChangedCB<D> = dyn Fn(u32, &mut D, *const spa_sys::spa_pod);
+type ParamChangedCB<D> = dyn Fn(u32, &mut D, Option<&Pod>);
 type ProcessCB<D> = dyn Fn(&Stream<D>, &mut D);
 
 pub struct ListenerLocalCallbacks<D> {
@@ -456,6 +556,11 @@ impl<D> ListenerLocalCallbacks<D> {
         ) {
             if let Some(state) = (data as *mut ListenerLocalCallbacks<D>).as_mut() {
                 if let Some(ref cb) = state.param_changed {
+                    let param = if !param.is_null() {
+                        Some(Pod::from_raw(param))
+                    } else {
+                        None
+                    };
                     cb(id, &mut state.user_data, param);
                 }
             }
@@ -564,6 +669,13 @@ pub trait ListenerBuilderT<D>: Sized {
     }
 
     /// Set the callback for the `io_changed` event.
+    ///
+    /// Callback parameters: id, area, size.
+    ///
+    /// This is how the stream receives its IO areas, such as the `spa_io_position` area
+    /// (`SPA_IO_Position`) holding the timeline position of the graph or the
+    /// `spa_io_rate_match` area (`SPA_IO_RateMatch`) used for rate compensation.
+    /// `area` is NULL when the area is removed.
     fn io_changed<F>(mut self, callback: F) -> Self
     where
         F: Fn(u32, *mut os::raw::c_void, u32) + 'static,
@@ -573,9 +685,13 @@ pub trait ListenerBuilderT<D>: Sized {
     }
 
     /// Set the callback for the `param_changed` event.
+    ///
+    /// Callback parameters: id, user data, param.
+    ///
+    /// `param` is `None` when the parameter was cleared, e.g. when the format is reset.
     fn param_changed<F>(mut self, callback: F) -> Self
     where
-        F: Fn(u32, &mut D, *const spa_sys::spa_pod) + 'static,
+        F: Fn(u32, &mut D, Option<&Pod>) + 'static,
     {
         self.callbacks().param_changed = Some(Box::new(callback));
         self
@@ -609,6 +725,8 @@ pub trait ListenerBuilderT<D>: Sized {
     }
 
     /// Set the callback for the `drained` event.
+    ///
+    /// It is emitted once the stream is drained after a call to [`Stream::flush()`].
     fn drained<F>(mut self, callback: F) -> Self
     where
         F: Fn() + 'static,
@@ -722,14 +840,23 @@ impl<D> std::ops::Drop for StreamListener<D> {
 bitflags! {
     /// Extra flags that can be used in [`Stream::connect()`]
     pub struct StreamFlags: pw_sys::pw_stream_flags {
+        /// Try to automatically connect the stream.
         const AUTOCONNECT = pw_sys::pw_stream_flags_PW_STREAM_FLAG_AUTOCONNECT;
+        /// Start the stream inactive, [`Stream::set_active()`] needs to be called explicitly.
         const INACTIVE = pw_sys::pw_stream_flags_PW_STREAM_FLAG_INACTIVE;
+        /// mmap the buffers except DmaBuf.
         const MAP_BUFFERS = pw_sys::pw_stream_flags_PW_STREAM_FLAG_MAP_BUFFERS;
+        /// Be a driver of the graph.
         const DRIVER = pw_sys::pw_stream_flags_PW_STREAM_FLAG_DRIVER;
+        /// Call `process` from the realtime thread.
         const RT_PROCESS = pw_sys::pw_stream_flags_PW_STREAM_FLAG_RT_PROCESS;
+        /// Don't convert the format.
         const NO_CONVERT = pw_sys::pw_stream_flags_PW_STREAM_FLAG_NO_CONVERT;
+        /// Require exclusive access to the device.
         const EXCLUSIVE = pw_sys::pw_stream_flags_PW_STREAM_FLAG_EXCLUSIVE;
+        /// Don't try to reconnect this stream when the sink/source is removed.
         const DONT_RECONNECT = pw_sys::pw_stream_flags_PW_STREAM_FLAG_DONT_RECONNECT;
+        /// The application will allocate the buffer memory.
         const ALLOC_BUFFERS = pw_sys::pw_stream_flags_PW_STREAM_FLAG_ALLOC_BUFFERS;
     }
 }